@@ -1,6 +1,6 @@
 use criterion::{criterion_group, criterion_main, Criterion, BenchmarkId};
 use std::hint::black_box;
-use zyphyr::{Vector, VectorCollection, DistanceMetric};
+use zyphyr::{Vector, VectorCollection, DistanceMetric, Euclidean, SearchOptions};
 use rand::{Rng, SeedableRng};
 use rand::rngs::StdRng;
 
@@ -65,7 +65,8 @@ fn bench_vector_operations(c: &mut Criterion) {
         group.bench_with_input(BenchmarkId::new("normalize", dim), dim, |b, _| {
             b.iter(|| {
                 let mut v_clone = v.clone();
-                black_box(v_clone.normalize())
+                v_clone.normalize();
+                black_box(v_clone)
             });
         });
         
@@ -89,7 +90,7 @@ fn bench_collection_operations(c: &mut Criterion) {
     // Benchmark insertion
     group.bench_function("insert_1000_vectors", |b| {
         b.iter_batched(
-            || VectorCollection::new(),
+            VectorCollection::new,
             |mut collection| {
                 for i in 0..1000 {
                     let v = generate_random_vector(&format!("v{}", i), dim, &mut rng);
@@ -194,11 +195,250 @@ fn bench_parallel_operations(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_cosine_norm_cache(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let dim = 128;
+    
+    let mut group = c.benchmark_group("cosine_norm_cache");
+    
+    let vectors: Vec<Vector> = (0..10_000)
+        .map(|i| generate_random_vector(&format!("v{}", i), dim, &mut rng))
+        .collect();
+    let query = generate_random_vector("query", dim, &mut rng);
+    
+    // Baseline: both magnitudes recomputed on every comparison
+    group.bench_function("uncached_scan_10k", |b| {
+        b.iter(|| {
+            let distances: Vec<f32> = vectors
+                .iter()
+                .map(|v| DistanceMetric::Cosine.compute(&query, v).unwrap())
+                .collect();
+            black_box(distances)
+        });
+    });
+    
+    // Same scan with norms cached, as the collection does on insert
+    let mut cached_query = query.clone();
+    cached_query.norm();
+    let mut collection = VectorCollection::with_capacity(vectors.len());
+    collection.batch_insert(vectors.clone()).unwrap();
+    group.bench_function("cached_scan_10k", |b| {
+        b.iter(|| {
+            let distances: Vec<f32> = collection
+                .iter()
+                .map(|v| DistanceMetric::Cosine.compute(&cached_query, v).unwrap())
+                .collect();
+            black_box(distances)
+        });
+    });
+    
+    group.bench_function("cached_search_10k", |b| {
+        b.iter(|| {
+            black_box(
+                collection.search(&query, 10, DistanceMetric::Cosine).unwrap()
+            )
+        });
+    });
+    
+    group.finish();
+}
+
+#[cfg(feature = "rayon")]
+fn bench_parallel_search(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let dim = 128;
+    
+    let mut group = c.benchmark_group("parallel_search");
+    group.sample_size(20);
+    
+    let mut collection = VectorCollection::with_capacity(100_000);
+    for i in 0..100_000 {
+        let v = generate_random_vector(&format!("v{}", i), dim, &mut rng);
+        collection.insert(v).unwrap();
+    }
+    let query = generate_random_vector("query", dim, &mut rng);
+    
+    group.bench_function("serial_search_100k", |b| {
+        b.iter(|| {
+            black_box(
+                collection.search(&query, 10, DistanceMetric::Euclidean).unwrap()
+            )
+        });
+    });
+    
+    group.bench_function("parallel_search_100k", |b| {
+        b.iter(|| {
+            black_box(
+                collection.search_parallel(&query, 10, DistanceMetric::Euclidean).unwrap()
+            )
+        });
+    });
+    
+    group.finish();
+}
+
+#[cfg(not(feature = "rayon"))]
+fn bench_parallel_search(_c: &mut Criterion) {}
+
+// SIMD dot product (via the metric) against a plain scalar loop over the same data
+fn bench_dot_product_simd(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut group = c.benchmark_group("dot_product_simd");
+    
+    for dim in [128, 512, 1024].iter() {
+        let v1 = generate_random_vector("v1", *dim, &mut rng);
+        let v2 = generate_random_vector("v2", *dim, &mut rng);
+        
+        group.bench_with_input(BenchmarkId::new("scalar", dim), dim, |b, _| {
+            b.iter(|| {
+                black_box(
+                    black_box(v1.data()).iter().zip(black_box(v2.data())).map(|(x, y)| x * y).sum::<f32>()
+                )
+            });
+        });
+        
+        group.bench_with_input(BenchmarkId::new("simd", dim), dim, |b, _| {
+            b.iter(|| {
+                black_box(
+                    DistanceMetric::DotProduct.compute(&v1, &v2).unwrap()
+                )
+            });
+        });
+    }
+    group.finish();
+}
+
+// Bounded-heap top-k selection over a large collection
+fn bench_top_k_search(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let dim = 128;
+    
+    let mut collection = VectorCollection::with_capacity(100_000);
+    for i in 0..100_000 {
+        collection.insert(generate_random_vector(&format!("v{}", i), dim, &mut rng)).unwrap();
+    }
+    let query = generate_random_vector("query", dim, &mut rng);
+    
+    let mut group = c.benchmark_group("top_k_search");
+    group.sample_size(10);
+    group.bench_function("k10_100k_vectors", |b| {
+        b.iter(|| {
+            black_box(
+                collection.search(&query, 10, DistanceMetric::Euclidean).unwrap()
+            )
+        });
+    });
+    group.finish();
+}
+
+fn bench_generic_search(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let dim = 128;
+    
+    let mut collection = VectorCollection::with_capacity(50_000);
+    for i in 0..50_000 {
+        collection.insert(generate_random_vector(&format!("v{}", i), dim, &mut rng)).unwrap();
+    }
+    let query = generate_random_vector("query", dim, &mut rng);
+    
+    let mut group = c.benchmark_group("generic_vs_enum_search");
+    group.sample_size(10);
+    group.bench_function("enum_dispatch", |b| {
+        b.iter(|| black_box(collection.search(&query, 10, DistanceMetric::Euclidean).unwrap()));
+    });
+    group.bench_function("generic", |b| {
+        b.iter(|| black_box(collection.search_generic::<Euclidean>(&query, 10).unwrap()));
+    });
+    group.finish();
+}
+
+// Fused single-pass SIMD cosine versus three scalar passes, with no cached norms
+fn bench_cosine_fused(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let dim = 1024;
+    let v1 = generate_random_vector("v1", dim, &mut rng);
+    let v2 = generate_random_vector("v2", dim, &mut rng);
+    
+    let mut group = c.benchmark_group("cosine_fused");
+    group.bench_function("scalar_1024", |b| {
+        b.iter(|| {
+            let (x, y) = (black_box(v1.data()), black_box(v2.data()));
+            let dot: f32 = x.iter().zip(y).map(|(a, b)| a * b).sum();
+            let x_mag = x.iter().map(|a| a * a).sum::<f32>().sqrt();
+            let y_mag = y.iter().map(|b| b * b).sum::<f32>().sqrt();
+            black_box(1.0 - dot / (x_mag * y_mag))
+        });
+    });
+    group.bench_function("fused_1024", |b| {
+        b.iter(|| black_box(DistanceMetric::Cosine.compute(&v1, &v2).unwrap()));
+    });
+    group.finish();
+}
+
+// Brute-force search with and without prefetching the next vector, on a
+// collection far larger than the cache
+fn bench_search_prefetch(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let dim = 512;
+    
+    let mut collection = VectorCollection::with_capacity(200_000);
+    for i in 0..200_000 {
+        collection.insert(generate_random_vector(&format!("v{}", i), dim, &mut rng)).unwrap();
+    }
+    let query = generate_random_vector("query", dim, &mut rng);
+    
+    let mut group = c.benchmark_group("search_prefetch");
+    group.sample_size(10);
+    for prefetch in [false, true] {
+        let options = SearchOptions { prefetch, ..SearchOptions::default() };
+        let name = if prefetch { "prefetch" } else { "no_prefetch" };
+        group.bench_function(name, |b| {
+            b.iter(|| black_box(collection.search_with_options(&query, 10, DistanceMetric::Euclidean, &options).unwrap()));
+        });
+    }
+    group.finish();
+}
+
+// Tiled batch_distance versus mapping compute over each vector
+fn bench_batch_distance_tiled(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let dim = 128;
+    let query = generate_random_vector("query", dim, &mut rng);
+    let vectors: Vec<Vector> = (0..50_000)
+        .map(|i| generate_random_vector(&format!("v{}", i), dim, &mut rng))
+        .collect();
+    let vector_refs: Vec<&Vector> = vectors.iter().collect();
+    
+    let mut group = c.benchmark_group("batch_distance_50k");
+    group.sample_size(20);
+    group.bench_function("naive_map", |b| {
+        b.iter(|| {
+            let distances: Vec<f32> = vector_refs
+                .iter()
+                .map(|v| DistanceMetric::Euclidean.compute(&query, v).unwrap())
+                .collect();
+            black_box(distances)
+        });
+    });
+    group.bench_function("tiled", |b| {
+        b.iter(|| black_box(query.batch_distance(&vector_refs, DistanceMetric::Euclidean).unwrap()));
+    });
+    group.finish();
+}
+
 criterion_group!(
     benches, 
     bench_distance_calculation, 
     bench_vector_operations,
     bench_collection_operations,
-    bench_parallel_operations
+    bench_parallel_operations,
+    bench_parallel_search,
+    bench_cosine_norm_cache,
+    bench_dot_product_simd,
+    bench_top_k_search,
+    bench_generic_search,
+    bench_cosine_fused,
+    bench_search_prefetch,
+    bench_batch_distance_tiled
 );
 criterion_main!(benches);
\ No newline at end of file
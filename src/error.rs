@@ -4,10 +4,20 @@ use thiserror::Error;
 pub enum ZyphyrError {
     #[error("Invalid vector dimension: expected {expected}, got {got}")]
     InvalidDimension { expected: usize, got: usize },
+    #[error("Invalid vector data: {0}")]
+    InvalidData(String),
     #[error("Vector ID not found: {0}")]
     IdNotFound(String),
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+    #[error("Corrupt data: {0}")]
+    Corrupt(String),
+    #[error("Memory limit exceeded: {used} bytes in use, limit is {limit}")]
+    MemoryLimitExceeded { used: usize, limit: usize },
+    #[error("Allocation failed: {0}")]
+    AllocationFailed(String),
+    #[error("Serialization error: {0}")]
+    Serialization(String),
     #[error("Other error: {0}")]
     Other(String),
 }
\ No newline at end of file
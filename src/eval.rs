@@ -0,0 +1,39 @@
+//! Helpers for measuring approximate search quality against exact results
+
+use std::collections::HashSet;
+
+/// Fraction of the exact top-`k` ids that also appear in the approximate top-`k`.
+/// Only the first `k` entries of each list are considered; if the exact list has
+/// fewer than `k` entries, recall is measured against however many it has, and an
+/// empty exact list gives a recall of 1.0.
+pub fn recall_at_k(exact: &[(String, f32)], approx: &[(String, f32)], k: usize) -> f32 {
+    let truth: HashSet<&str> = exact.iter().take(k).map(|(id, _)| id.as_str()).collect();
+    if truth.is_empty() {
+        return 1.0;
+    }
+    let hits = approx
+        .iter()
+        .take(k)
+        .filter(|(id, _)| truth.contains(id.as_str()))
+        .count();
+    hits as f32 / truth.len() as f32
+}
+
+/// Average [`recall_at_k`] over paired per-query result lists.
+/// Returns 0.0 when there are no queries.
+///
+/// # Panics
+///
+/// Panics if `exact` and `approx` have different numbers of queries.
+pub fn mean_recall_at_k(exact: &[Vec<(String, f32)>], approx: &[Vec<(String, f32)>], k: usize) -> f32 {
+    assert_eq!(exact.len(), approx.len(), "exact and approximate result sets must be paired");
+    if exact.is_empty() {
+        return 0.0;
+    }
+    let total: f32 = exact
+        .iter()
+        .zip(approx)
+        .map(|(e, a)| recall_at_k(e, a, k))
+        .sum();
+    total / exact.len() as f32
+}
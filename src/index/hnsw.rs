@@ -0,0 +1,589 @@
+use crate::{Vector, ZyphyrError, DistanceMetric};
+use crate::eval::mean_recall_at_k;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+// `build_auto` tuning: held-out query count, neighbors scored per query, the
+// M values tried in order, the construction beam, and the ef_search ceiling
+const AUTO_SAMPLE: usize = 100;
+const AUTO_K: usize = 10;
+const AUTO_M: [usize; 4] = [16, 24, 32, 48];
+const AUTO_EF_CONSTRUCTION: usize = 200;
+const AUTO_MAX_EF: usize = 512;
+
+// Report build progress about once per percent of the input
+pub(super) fn progress_interval(total: usize) -> usize {
+    (total / 100).max(1)
+}
+
+/// A node reference paired with its distance to the current query
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Candidate {
+    pub(super) distance: f32,
+    pub(super) index: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .partial_cmp(&other.distance)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| self.index.cmp(&other.index))
+    }
+}
+
+/// Hierarchical Navigable Small World graph for approximate nearest neighbor search
+pub struct HnswIndex {
+    vectors: Vec<Vector>,
+    id_to_index: HashMap<String, usize>,
+    // neighbors[node][layer] holds the adjacency list of `node` on `layer`
+    neighbors: Vec<Vec<Vec<usize>>>,
+    deleted: Vec<bool>,    // Tombstones: still traversed for connectivity, never returned
+    deleted_count: usize,
+    entry_point: Option<usize>,
+    max_level: usize,
+    dimensions: Option<usize>,
+    m: usize,
+    m_max0: usize,         // Layer 0 keeps twice as many links, as in the paper
+    ef_construction: usize,
+    ef_search: usize,      // Beam width for `search_default`, tuned by `build_auto`
+    level_mult: f64,       // Normalization factor for level generation (1 / ln(M))
+    metric: DistanceMetric,
+    rng: StdRng,           // Seeded level generator, so builds and inserts are reproducible
+}
+
+impl HnswIndex {
+    /// Build an index over `vectors`, linking each node to up to `m` neighbors per layer.
+    ///
+    /// `seed` drives the random level assignment, and distance ties are broken by
+    /// node order, so the same seed and input order always produce the same graph.
+    pub fn build(
+        vectors: &[Vector],
+        m: usize,
+        ef_construction: usize,
+        metric: DistanceMetric,
+        seed: u64,
+    ) -> Result<Self, ZyphyrError> {
+        Self::build_with_progress(vectors, m, ef_construction, metric, seed, |_, _| {})
+    }
+
+    /// Like [`HnswIndex::build`], reporting `(processed, total)` to `progress`
+    /// roughly every 1% of the input and once more with `(total, total)` at the end.
+    ///
+    /// The callback runs synchronously on the building thread, between inserts,
+    /// and is never invoked concurrently. It doesn't need to be `Send` or `Sync`,
+    /// but it delays the build for as long as it runs, so hand slow work (e.g.
+    /// redrawing a UI) off to another thread.
+    pub fn build_with_progress(
+        vectors: &[Vector],
+        m: usize,
+        ef_construction: usize,
+        metric: DistanceMetric,
+        seed: u64,
+        mut progress: impl FnMut(usize, usize),
+    ) -> Result<Self, ZyphyrError> {
+        if m < 2 {
+            return Err(ZyphyrError::Other(format!("HNSW parameter M must be at least 2, got {}", m)));
+        }
+        
+        let mut index = HnswIndex {
+            vectors: Vec::with_capacity(vectors.len()),
+            id_to_index: HashMap::with_capacity(vectors.len()),
+            neighbors: Vec::with_capacity(vectors.len()),
+            deleted: Vec::with_capacity(vectors.len()),
+            deleted_count: 0,
+            entry_point: None,
+            max_level: 0,
+            dimensions: None,
+            m,
+            m_max0: m * 2,
+            ef_construction: ef_construction.max(m),
+            ef_search: ef_construction.max(m),
+            level_mult: 1.0 / (m as f64).ln(),
+            metric,
+            rng: StdRng::seed_from_u64(seed),
+        };
+        
+        let total = vectors.len();
+        let interval = progress_interval(total);
+        for (processed, vector) in vectors.iter().enumerate() {
+            let level = index.random_level();
+            index.add_node(vector.clone(), level)?;
+            if (processed + 1) % interval == 0 && processed + 1 < total {
+                progress(processed + 1, total);
+            }
+        }
+        progress(total, total);
+        Ok(index)
+    }
+
+    /// Build an index tuned to reach `target_recall` (recall@10, in `(0, 1]`)
+    /// without hand-picking parameters.
+    ///
+    /// Up to 100 vectors, drawn at random with `seed`, are held out as queries
+    /// and the rest are indexed. For each M in 16, 24, 32, 48 it bisects the
+    /// smallest `ef_search` (at most 512) whose measured recall against brute
+    /// force meets the target. The first M that gets there wins, and the
+    /// held-out vectors are then inserted too. If none does, the largest M with
+    /// `ef_search` 512 is returned. The chosen beam is used by
+    /// [`HnswIndex::search_default`]. The same `seed` and input give the same index.
+    pub fn build_auto(
+        vectors: &[Vector],
+        target_recall: f32,
+        metric: DistanceMetric,
+        seed: u64,
+    ) -> Result<Self, ZyphyrError> {
+        if !(target_recall > 0.0 && target_recall <= 1.0) {
+            return Err(ZyphyrError::Other(format!("target recall must be in (0, 1], got {}", target_recall)));
+        }
+        
+        // Hold out a random sample, keeping at least half for the graph
+        let sample = AUTO_SAMPLE.min(vectors.len() / 2);
+        if sample == 0 {
+            return Self::build(vectors, AUTO_M[0], AUTO_EF_CONSTRUCTION, metric, seed);
+        }
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut held_out = vec![false; vectors.len()];
+        for i in rand::seq::index::sample(&mut rng, vectors.len(), sample) {
+            held_out[i] = true;
+        }
+        let mut queries = Vec::with_capacity(sample);
+        let mut indexed = Vec::with_capacity(vectors.len() - sample);
+        for (v, &held) in vectors.iter().zip(&held_out) {
+            if held {
+                queries.push(v);
+            } else {
+                indexed.push(v.clone());
+            }
+        }
+        
+        let k = AUTO_K.min(indexed.len());
+        let exact = queries
+            .iter()
+            .map(|query| brute_force_top_k(&indexed, query, k, &metric))
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        let mut index = Self::build(&indexed, AUTO_M[0], AUTO_EF_CONSTRUCTION, metric.clone(), seed)?;
+        let mut ef = index.smallest_ef_for(&queries, &exact, k, target_recall)?;
+        for &m in &AUTO_M[1..] {
+            if ef.is_some() {
+                break;
+            }
+            index = Self::build(&indexed, m, AUTO_EF_CONSTRUCTION, metric.clone(), seed)?;
+            ef = index.smallest_ef_for(&queries, &exact, k, target_recall)?;
+        }
+        
+        index.ef_search = ef.unwrap_or(AUTO_MAX_EF);
+        for query in queries {
+            index.insert(query.clone())?;
+        }
+        Ok(index)
+    }
+
+    // Bisect the smallest ef_search in [k, AUTO_MAX_EF] reaching `target` mean
+    // recall, or None if even the ceiling falls short
+    fn smallest_ef_for(
+        &self,
+        queries: &[&Vector],
+        exact: &[Vec<(String, f32)>],
+        k: usize,
+        target: f32,
+    ) -> Result<Option<usize>, ZyphyrError> {
+        let recall_at = |ef: usize| -> Result<f32, ZyphyrError> {
+            let approx = queries
+                .iter()
+                .map(|query| self.search(query, k, ef))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(mean_recall_at_k(exact, &approx, k))
+        };
+        
+        if recall_at(AUTO_MAX_EF)? < target {
+            return Ok(None);
+        }
+        let (mut low, mut high) = (k.max(1), AUTO_MAX_EF);
+        while low < high {
+            let mid = (low + high) / 2;
+            if recall_at(mid)? >= target {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        Ok(Some(high))
+    }
+
+    /// Add a single vector to an existing index. It is assigned a random top level
+    /// and linked into every layer up to it; the first vector ever inserted (or one
+    /// drawn above the current maximum level) becomes the new entry point. Levels
+    /// continue the build's seeded sequence, so replayed inserts are reproducible.
+    pub fn insert(&mut self, vector: Vector) -> Result<(), ZyphyrError> {
+        let level = self.random_level();
+        self.add_node(vector, level)
+    }
+
+    /// Return the `k` approximate nearest neighbors of `query`, exploring `ef_search` candidates
+    pub fn search(
+        &self,
+        query: &Vector,
+        k: usize,
+        ef_search: usize,
+    ) -> Result<Vec<(String, f32)>, ZyphyrError> {
+        let entry = match self.entry_point {
+            Some(entry) => entry,
+            None => return Ok(Vec::new()),
+        };
+        if let Some(dims) = self.dimensions
+            && query.dim() != dims
+        {
+            return Err(ZyphyrError::InvalidDimension { expected: dims, got: query.dim() });
+        }
+        
+        // Greedy descent through the upper layers
+        let mut current = Candidate { distance: self.distance(query, entry)?, index: entry };
+        for layer in (1..=self.max_level).rev() {
+            current = self.search_layer(query, &[current], 1, layer, false)?[0];
+        }
+        
+        // Tombstones are kept out of the beam itself, so removals can't crowd live
+        // vectors out of the `ef` candidates
+        let ef = ef_search.max(k);
+        let candidates = self.search_layer(query, &[current], ef, 0, true)?;
+        Ok(candidates
+            .into_iter()
+            .take(k)
+            .map(|c| (self.vectors[c.index].id().to_string(), c.distance))
+            .collect())
+    }
+
+    /// [`HnswIndex::search`] with the index's own `ef_search`: the value chosen by
+    /// [`HnswIndex::build_auto`], or `ef_construction` for other builds
+    pub fn search_default(&self, query: &Vector, k: usize) -> Result<Vec<(String, f32)>, ZyphyrError> {
+        self.search(query, k, self.ef_search)
+    }
+
+    /// The beam width used by [`HnswIndex::search_default`]
+    pub fn ef_search(&self) -> usize {
+        self.ef_search
+    }
+
+    /// Tombstone the vector with `id` so it is no longer returned by searches.
+    /// Its neighbors are relinked to each other to keep the graph connected.
+    /// Returns `Ok(false)` if the id was not present.
+    pub fn remove(&mut self, id: &str) -> Result<bool, ZyphyrError> {
+        let node = match self.id_to_index.remove(id) {
+            Some(node) => node,
+            None => return Ok(false),
+        };
+        self.deleted[node] = true;
+        self.deleted_count += 1;
+        
+        for layer in 0..self.neighbors[node].len() {
+            let links = self.neighbors[node][layer].clone();
+            for &neighbor in &links {
+                if self.deleted[neighbor] {
+                    continue;
+                }
+                self.repair(neighbor, node, &links, layer)?;
+            }
+        }
+        
+        if self.entry_point == Some(node) {
+            // Promote the live node with the highest level, if any remain
+            let replacement = (0..self.vectors.len())
+                .filter(|&i| !self.deleted[i])
+                .max_by_key(|&i| self.neighbors[i].len());
+            self.entry_point = replacement;
+            self.max_level = replacement.map_or(0, |i| self.neighbors[i].len() - 1);
+        }
+        Ok(true)
+    }
+
+    /// Rebuild the graph from the live vectors when tombstones make up more than
+    /// `max_deleted_fraction` of the nodes. Returns whether a rebuild happened.
+    pub fn rebuild_if_needed(&mut self, max_deleted_fraction: f32) -> Result<bool, ZyphyrError> {
+        if self.vectors.is_empty()
+            || (self.deleted_count as f32 / self.vectors.len() as f32) <= max_deleted_fraction
+        {
+            return Ok(false);
+        }
+        
+        let live: Vec<Vector> = self.vectors
+            .iter()
+            .zip(&self.deleted)
+            .filter(|(_, deleted)| !**deleted)
+            .map(|(v, _)| v.clone())
+            .collect();
+        // Seed the rebuild from our own generator so the result stays reproducible
+        let seed = self.rng.random();
+        let mut rebuilt = HnswIndex::build(&live, self.m, self.ef_construction, self.metric.clone(), seed)?;
+        rebuilt.dimensions = self.dimensions;
+        rebuilt.ef_search = self.ef_search;
+        *self = rebuilt;
+        Ok(true)
+    }
+
+    /// Number of live (non-removed) vectors
+    pub fn len(&self) -> usize {
+        self.vectors.len() - self.deleted_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Fraction of graph nodes that are tombstones
+    pub fn deleted_fraction(&self) -> f32 {
+        if self.vectors.is_empty() {
+            0.0
+        } else {
+            self.deleted_count as f32 / self.vectors.len() as f32
+        }
+    }
+
+    pub fn metric(&self) -> DistanceMetric {
+        self.metric.clone()
+    }
+
+    // The entry point and every node's per-layer adjacency lists, for comparing graphs
+    #[cfg(test)]
+    pub(crate) fn graph(&self) -> (Option<usize>, &[Vec<Vec<usize>>]) {
+        (self.entry_point, &self.neighbors)
+    }
+
+    // Draw a level from the exponentially decaying distribution floor(-ln(U) * mL)
+    fn random_level(&mut self) -> usize {
+        let uniform: f64 = self.rng.random_range(f64::EPSILON..1.0);
+        (-uniform.ln() * self.level_mult).floor() as usize
+    }
+
+    fn distance(&self, query: &Vector, index: usize) -> Result<f32, ZyphyrError> {
+        self.metric.compute(query, &self.vectors[index])
+    }
+
+    fn max_connections(&self, layer: usize) -> usize {
+        if layer == 0 { self.m_max0 } else { self.m }
+    }
+
+    // Insert a vector at the given top level and wire it into every layer below
+    fn add_node(&mut self, vector: Vector, level: usize) -> Result<(), ZyphyrError> {
+        if let Some(dims) = self.dimensions {
+            if vector.dim() != dims {
+                return Err(ZyphyrError::InvalidDimension { expected: dims, got: vector.dim() });
+            }
+        } else {
+            self.dimensions = Some(vector.dim());
+        }
+        if self.id_to_index.contains_key(vector.id()) {
+            return Err(ZyphyrError::Other(format!("Duplicate ID: {}", vector.id())));
+        }
+        
+        let node = self.vectors.len();
+        self.id_to_index.insert(vector.id().to_string(), node);
+        self.vectors.push(vector);
+        self.neighbors.push(vec![Vec::new(); level + 1]);
+        self.deleted.push(false);
+        
+        let entry = match self.entry_point {
+            Some(entry) => entry,
+            None => {
+                // The very first node becomes the entry point
+                self.entry_point = Some(node);
+                self.max_level = level;
+                return Ok(());
+            }
+        };
+        
+        let query = self.vectors[node].clone();
+        let mut current = vec![Candidate { distance: self.distance(&query, entry)?, index: entry }];
+        
+        // Greedy descent through layers above the new node's level
+        for layer in (level + 1..=self.max_level).rev() {
+            current = vec![self.search_layer(&query, &current, 1, layer, false)?[0]];
+        }
+        
+        // Connect the node on every layer it participates in
+        for layer in (0..=level.min(self.max_level)).rev() {
+            let candidates = self.search_layer(&query, &current, self.ef_construction, layer, false)?;
+            let selected = self.select_neighbors(&candidates, self.m)?;
+            self.neighbors[node][layer] = selected.clone();
+            
+            for &neighbor in &selected {
+                self.neighbors[neighbor][layer].push(node);
+                if self.neighbors[neighbor][layer].len() > self.max_connections(layer) {
+                    self.prune(neighbor, layer)?;
+                }
+            }
+            current = candidates;
+        }
+        
+        if level > self.max_level {
+            self.max_level = level;
+            self.entry_point = Some(node);
+        }
+        Ok(())
+    }
+
+    // Drop the link from `node` to the removed node and offer the removed node's
+    // other neighbors as replacements, keeping the best by the selection heuristic
+    fn repair(&mut self, node: usize, removed: usize, replacements: &[usize], layer: usize) -> Result<(), ZyphyrError> {
+        let mut links: Vec<usize> = self.neighbors[node][layer]
+            .iter()
+            .copied()
+            .filter(|&index| index != removed)
+            .collect();
+        for &candidate in replacements {
+            if candidate != node && !self.deleted[candidate] && !links.contains(&candidate) {
+                links.push(candidate);
+            }
+        }
+        
+        let base = &self.vectors[node];
+        let mut candidates = links
+            .iter()
+            .map(|&index| {
+                let distance = self.metric.compute(base, &self.vectors[index])?;
+                Ok(Candidate { distance, index })
+            })
+            .collect::<Result<Vec<_>, ZyphyrError>>()?;
+        candidates.sort();
+        self.neighbors[node][layer] = self.select_neighbors(&candidates, self.max_connections(layer))?;
+        Ok(())
+    }
+
+    // Shrink an over-full adjacency list back down to the layer's limit
+    fn prune(&mut self, node: usize, layer: usize) -> Result<(), ZyphyrError> {
+        let base = &self.vectors[node];
+        let mut candidates = self.neighbors[node][layer]
+            .iter()
+            .map(|&index| {
+                let distance = self.metric.compute(base, &self.vectors[index])?;
+                Ok(Candidate { distance, index })
+            })
+            .collect::<Result<Vec<_>, ZyphyrError>>()?;
+        candidates.sort();
+        self.neighbors[node][layer] = self.select_neighbors(&candidates, self.max_connections(layer))?;
+        Ok(())
+    }
+
+    // Neighbor selection heuristic: prefer candidates that are closer to the base
+    // than to any already selected neighbor, which keeps the graph navigable across
+    // clusters. Pruned candidates backfill any remaining slots.
+    // `candidates` must be sorted by ascending distance.
+    fn select_neighbors(&self, candidates: &[Candidate], m: usize) -> Result<Vec<usize>, ZyphyrError> {
+        let mut selected: Vec<usize> = Vec::with_capacity(m);
+        let mut pruned: Vec<usize> = Vec::new();
+        
+        for candidate in candidates {
+            if selected.len() >= m {
+                break;
+            }
+            let mut keep = true;
+            for &chosen in &selected {
+                let distance = self.metric.compute(&self.vectors[candidate.index], &self.vectors[chosen])?;
+                if distance < candidate.distance {
+                    keep = false;
+                    break;
+                }
+            }
+            if keep {
+                selected.push(candidate.index);
+            } else {
+                pruned.push(candidate.index);
+            }
+        }
+        
+        for index in pruned {
+            if selected.len() >= m {
+                break;
+            }
+            selected.push(index);
+        }
+        Ok(selected)
+    }
+
+    // Best-first search on a single layer, returning up to `ef` candidates sorted ascending.
+    // With `live_only`, tombstones are still expanded (they keep the graph connected)
+    // but never take a result slot.
+    fn search_layer(
+        &self,
+        query: &Vector,
+        entry_points: &[Candidate],
+        ef: usize,
+        layer: usize,
+        live_only: bool,
+    ) -> Result<Vec<Candidate>, ZyphyrError> {
+        let eligible = |index: usize| !live_only || !self.deleted[index];
+        let mut visited: HashSet<usize> = entry_points.iter().map(|c| c.index).collect();
+        // Min-heap of nodes to expand and max-heap of the best results so far
+        let mut to_visit: BinaryHeap<Reverse<Candidate>> =
+            entry_points.iter().map(|&c| Reverse(c)).collect();
+        let mut results: BinaryHeap<Candidate> =
+            entry_points.iter().copied().filter(|c| eligible(c.index)).collect();
+        while results.len() > ef {
+            results.pop();
+        }
+        
+        while let Some(Reverse(closest)) = to_visit.pop() {
+            let furthest = results.peek().map_or(f32::INFINITY, |c| c.distance);
+            if closest.distance > furthest && results.len() >= ef {
+                break;
+            }
+            
+            let links = match self.neighbors[closest.index].get(layer) {
+                Some(links) => links,
+                None => continue,
+            };
+            for &neighbor in links {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let distance = self.distance(query, neighbor)?;
+                let furthest = results.peek().map_or(f32::INFINITY, |c| c.distance);
+                if results.len() < ef || distance < furthest {
+                    let candidate = Candidate { distance, index: neighbor };
+                    to_visit.push(Reverse(candidate));
+                    if eligible(neighbor) {
+                        results.push(candidate);
+                        if results.len() > ef {
+                            results.pop();
+                        }
+                    }
+                }
+            }
+        }
+        
+        Ok(results.into_sorted_vec())
+    }
+}
+
+// Exact top-k by scoring every vector, ties broken by id like collection search
+fn brute_force_top_k(
+    vectors: &[Vector],
+    query: &Vector,
+    k: usize,
+    metric: &DistanceMetric,
+) -> Result<Vec<(String, f32)>, ZyphyrError> {
+    let mut scored = vectors
+        .iter()
+        .map(|v| Ok((v.id().to_string(), metric.compute(query, v)?)))
+        .collect::<Result<Vec<_>, ZyphyrError>>()?;
+    scored.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal).then_with(|| a.0.cmp(&b.0)));
+    scored.truncate(k);
+    Ok(scored)
+}
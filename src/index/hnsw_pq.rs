@@ -0,0 +1,338 @@
+use crate::{Vector, ZyphyrError, DistanceMetric, ProductQuantizer, PqCode};
+use crate::index::hnsw::{Candidate, progress_interval};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// HNSW graph over product-quantized vectors (Euclidean distance only).
+///
+/// Each node stores an `m`-byte [`PqCode`] instead of its full vector. Graph
+/// construction and traversal use asymmetric distances (the raw vector being
+/// inserted or searched against PQ codes); links between stored nodes are
+/// pruned with symmetric code-to-code distances. If the originals are kept,
+/// the best `rerank_k` candidates of a search are re-scored exactly.
+pub struct HnswPqIndex {
+    pq: ProductQuantizer,
+    codes: Vec<PqCode>,
+    originals: Option<Vec<Vector>>,  // Full-precision copies for reranking, if kept
+    id_to_index: HashMap<String, usize>,
+    // neighbors[node][layer] holds the adjacency list of `node` on `layer`
+    neighbors: Vec<Vec<Vec<usize>>>,
+    // symmetric[subspace][a * ksub + b] is the squared distance between centroids a and b
+    symmetric: Vec<Vec<f32>>,
+    entry_point: Option<usize>,
+    max_level: usize,
+    m: usize,
+    m_max0: usize,
+    ef_construction: usize,
+    level_mult: f64,
+}
+
+impl HnswPqIndex {
+    /// Train a product quantizer with `pq_m` subspaces of `2^pq_nbits` centroids
+    /// on `vectors`, then build an HNSW graph with up to `m` links per layer over
+    /// their codes. Set `keep_originals` to retain full vectors for reranking.
+    /// `seed` drives both PQ training and level assignment, so the same seed and
+    /// input order always produce the same index.
+    pub fn build(
+        vectors: &[Vector],
+        m: usize,
+        ef_construction: usize,
+        pq_m: usize,
+        pq_nbits: usize,
+        keep_originals: bool,
+        seed: u64,
+    ) -> Result<Self, ZyphyrError> {
+        Self::build_with_progress(vectors, m, ef_construction, pq_m, pq_nbits, keep_originals, seed, |_, _| {})
+    }
+
+    /// Like [`HnswPqIndex::build`], reporting graph construction progress as
+    /// `(processed, total)`. The callback follows the same contract as
+    /// [`crate::HnswIndex::build_with_progress`]: it runs on the building thread
+    /// and is never called concurrently. PQ training happens before the first call.
+    #[allow(clippy::too_many_arguments)]
+    pub fn build_with_progress(
+        vectors: &[Vector],
+        m: usize,
+        ef_construction: usize,
+        pq_m: usize,
+        pq_nbits: usize,
+        keep_originals: bool,
+        seed: u64,
+        mut progress: impl FnMut(usize, usize),
+    ) -> Result<Self, ZyphyrError> {
+        if m < 2 {
+            return Err(ZyphyrError::Other(format!("HNSW parameter M must be at least 2, got {}", m)));
+        }
+        
+        let pq = ProductQuantizer::train(vectors, pq_m, pq_nbits, seed)?;
+        let symmetric = symmetric_tables(&pq);
+        let mut index = HnswPqIndex {
+            pq,
+            codes: Vec::with_capacity(vectors.len()),
+            originals: None,
+            id_to_index: HashMap::with_capacity(vectors.len()),
+            neighbors: Vec::with_capacity(vectors.len()),
+            symmetric,
+            entry_point: None,
+            max_level: 0,
+            m,
+            m_max0: m * 2,
+            ef_construction: ef_construction.max(m),
+            level_mult: 1.0 / (m as f64).ln(),
+        };
+        
+        let total = vectors.len();
+        let interval = progress_interval(total);
+        let mut rng = StdRng::seed_from_u64(seed);
+        for (processed, vector) in vectors.iter().enumerate() {
+            let uniform: f64 = rng.random_range(f64::EPSILON..1.0);
+            let level = (-uniform.ln() * index.level_mult).floor() as usize;
+            index.add_node(vector, level)?;
+            if (processed + 1) % interval == 0 && processed + 1 < total {
+                progress(processed + 1, total);
+            }
+        }
+        progress(total, total);
+        if keep_originals {
+            index.originals = Some(vectors.to_vec());
+        }
+        Ok(index)
+    }
+
+    /// Return the `k` approximate nearest neighbors of `query`, exploring
+    /// `ef_search` candidates by PQ distance. When the originals were kept and
+    /// `rerank_k > 0`, the best `max(rerank_k, k)` candidates are re-scored with
+    /// exact Euclidean distance before the top `k` are taken; otherwise the
+    /// returned distances are the PQ approximations.
+    pub fn search(
+        &self,
+        query: &Vector,
+        k: usize,
+        ef_search: usize,
+        rerank_k: usize,
+    ) -> Result<Vec<(String, f32)>, ZyphyrError> {
+        let table = self.pq.distance_table(query)?;
+        let entry = match self.entry_point {
+            Some(entry) => entry,
+            None => return Ok(Vec::new()),
+        };
+        
+        let distance = |index: usize| table.distance(&self.codes[index]);
+        let mut current = Candidate { distance: distance(entry), index: entry };
+        for layer in (1..=self.max_level).rev() {
+            current = self.search_layer(&distance, &[current], 1, layer)[0];
+        }
+        
+        let ef = ef_search.max(k).max(rerank_k);
+        let candidates = self.search_layer(&distance, &[current], ef, 0);
+        
+        let mut results: Vec<(String, f32)> = match &self.originals {
+            Some(originals) if rerank_k > 0 => {
+                let mut reranked = candidates
+                    .iter()
+                    .take(rerank_k.max(k))
+                    .map(|c| {
+                        let exact = DistanceMetric::Euclidean.compute(query, &originals[c.index])?;
+                        Ok((originals[c.index].id().to_string(), exact))
+                    })
+                    .collect::<Result<Vec<_>, ZyphyrError>>()?;
+                reranked.sort_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+                reranked
+            }
+            _ => candidates
+                .iter()
+                .map(|c| (self.codes[c.index].id().to_string(), c.distance))
+                .collect(),
+        };
+        results.truncate(k);
+        Ok(results)
+    }
+
+    pub fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+
+    /// The trained quantizer whose codes make up the graph
+    pub fn quantizer(&self) -> &ProductQuantizer {
+        &self.pq
+    }
+
+    /// Whether full-precision vectors were kept, enabling reranking
+    pub fn has_originals(&self) -> bool {
+        self.originals.is_some()
+    }
+
+    fn max_connections(&self, layer: usize) -> usize {
+        if layer == 0 { self.m_max0 } else { self.m }
+    }
+
+    // Approximate distance between two stored nodes via the centroid tables
+    fn node_distance(&self, a: usize, b: usize) -> f32 {
+        let ksub = self.pq.ksub();
+        self.codes[a].codes()
+            .iter()
+            .zip(self.codes[b].codes())
+            .zip(&self.symmetric)
+            .map(|((&x, &y), table)| table[x as usize * ksub + y as usize])
+            .sum::<f32>()
+            .sqrt()
+    }
+
+    // Encode `vector` and wire it into every layer up to `level`, measuring
+    // distances from the raw vector to existing codes
+    fn add_node(&mut self, vector: &Vector, level: usize) -> Result<(), ZyphyrError> {
+        if self.id_to_index.contains_key(vector.id()) {
+            return Err(ZyphyrError::Other(format!("Duplicate ID: {}", vector.id())));
+        }
+        let table = self.pq.distance_table(vector)?;
+        
+        let node = self.codes.len();
+        self.id_to_index.insert(vector.id().to_string(), node);
+        self.codes.push(self.pq.encode(vector)?);
+        self.neighbors.push(vec![Vec::new(); level + 1]);
+        
+        let entry = match self.entry_point {
+            Some(entry) => entry,
+            None => {
+                self.entry_point = Some(node);
+                self.max_level = level;
+                return Ok(());
+            }
+        };
+        
+        let distance = |index: usize| table.distance(&self.codes[index]);
+        let mut current = vec![Candidate { distance: distance(entry), index: entry }];
+        for layer in (level + 1..=self.max_level).rev() {
+            current = vec![self.search_layer(&distance, &current, 1, layer)[0]];
+        }
+        
+        // Linking below needs `&mut self`, so the distance closure is rebuilt per layer
+        for layer in (0..=level.min(self.max_level)).rev() {
+            let distance = |index: usize| table.distance(&self.codes[index]);
+            let candidates = self.search_layer(&distance, &current, self.ef_construction, layer);
+            let selected = self.select_neighbors(&candidates, self.m);
+            self.neighbors[node][layer] = selected.clone();
+            
+            for &neighbor in &selected {
+                self.neighbors[neighbor][layer].push(node);
+                if self.neighbors[neighbor][layer].len() > self.max_connections(layer) {
+                    self.prune(neighbor, layer);
+                }
+            }
+            current = candidates;
+        }
+        
+        if level > self.max_level {
+            self.max_level = level;
+            self.entry_point = Some(node);
+        }
+        Ok(())
+    }
+
+    // Shrink an over-full adjacency list back down to the layer's limit
+    fn prune(&mut self, node: usize, layer: usize) {
+        let mut candidates: Vec<Candidate> = self.neighbors[node][layer]
+            .iter()
+            .map(|&index| Candidate { distance: self.node_distance(node, index), index })
+            .collect();
+        candidates.sort();
+        self.neighbors[node][layer] = self.select_neighbors(&candidates, self.max_connections(layer));
+    }
+
+    // Same diversity heuristic as `HnswIndex`, over code-to-code distances.
+    // `candidates` must be sorted by ascending distance.
+    fn select_neighbors(&self, candidates: &[Candidate], m: usize) -> Vec<usize> {
+        let mut selected: Vec<usize> = Vec::with_capacity(m);
+        let mut pruned: Vec<usize> = Vec::new();
+        
+        for candidate in candidates {
+            if selected.len() >= m {
+                break;
+            }
+            let dominated = selected
+                .iter()
+                .any(|&chosen| self.node_distance(candidate.index, chosen) < candidate.distance);
+            if dominated {
+                pruned.push(candidate.index);
+            } else {
+                selected.push(candidate.index);
+            }
+        }
+        
+        for index in pruned {
+            if selected.len() >= m {
+                break;
+            }
+            selected.push(index);
+        }
+        selected
+    }
+
+    // Best-first search on a single layer, returning up to `ef` candidates sorted ascending
+    fn search_layer(
+        &self,
+        distance: &impl Fn(usize) -> f32,
+        entry_points: &[Candidate],
+        ef: usize,
+        layer: usize,
+    ) -> Vec<Candidate> {
+        let mut visited: HashSet<usize> = entry_points.iter().map(|c| c.index).collect();
+        let mut to_visit: BinaryHeap<Reverse<Candidate>> =
+            entry_points.iter().map(|&c| Reverse(c)).collect();
+        let mut results: BinaryHeap<Candidate> = entry_points.iter().copied().collect();
+        while results.len() > ef {
+            results.pop();
+        }
+        
+        while let Some(Reverse(closest)) = to_visit.pop() {
+            let furthest = results.peek().map_or(f32::INFINITY, |c| c.distance);
+            if closest.distance > furthest && results.len() >= ef {
+                break;
+            }
+            
+            let links = match self.neighbors[closest.index].get(layer) {
+                Some(links) => links,
+                None => continue,
+            };
+            for &neighbor in links {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let candidate = Candidate { distance: distance(neighbor), index: neighbor };
+                let furthest = results.peek().map_or(f32::INFINITY, |c| c.distance);
+                if results.len() < ef || candidate.distance < furthest {
+                    to_visit.push(Reverse(candidate));
+                    results.push(candidate);
+                    if results.len() > ef {
+                        results.pop();
+                    }
+                }
+            }
+        }
+        
+        results.into_sorted_vec()
+    }
+}
+
+// Squared distances between every pair of centroids within each subspace
+fn symmetric_tables(pq: &ProductQuantizer) -> Vec<Vec<f32>> {
+    pq.codebooks()
+        .iter()
+        .map(|codebook| {
+            codebook
+                .iter()
+                .flat_map(|a| {
+                    codebook
+                        .iter()
+                        .map(move |b| a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum::<f32>())
+                })
+                .collect()
+        })
+        .collect()
+}
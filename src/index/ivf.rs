@@ -0,0 +1,146 @@
+use crate::{Vector, ZyphyrError, DistanceMetric};
+use super::kmeans::{kmeans_with_progress, nearest_centroid};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+/// Number of Lloyd iterations used when training the coarse quantizer
+const TRAIN_ITERATIONS: usize = 25;
+
+/// Inverted file index: vectors are bucketed by their nearest k-means centroid and
+/// queries only scan the buckets of the `nprobe` closest centroids
+pub struct IvfIndex {
+    vectors: Vec<Vector>,
+    centroids: Vec<Vec<f32>>,
+    lists: Vec<Vec<usize>>,  // Member vector indices per centroid
+    dimensions: Option<usize>,
+    metric: DistanceMetric,
+}
+
+impl IvfIndex {
+    /// Cluster `vectors` into `nlist` inverted lists. `seed` drives the k-means++
+    /// initialization, so the same seed and input always give the same lists.
+    pub fn train(
+        vectors: &[Vector],
+        nlist: usize,
+        metric: DistanceMetric,
+        seed: u64,
+    ) -> Result<Self, ZyphyrError> {
+        Self::train_with_progress(vectors, nlist, metric, seed, |_, _| {})
+    }
+
+    /// Like [`IvfIndex::train`], reporting `(processed, total)` to `progress`. The
+    /// steps are the k-means iterations followed by bucketing the vectors into
+    /// their lists; if k-means converges early the count jumps ahead. The callback
+    /// follows the same contract as [`crate::HnswIndex::build_with_progress`]: it
+    /// runs on the training thread and is never called concurrently.
+    pub fn train_with_progress(
+        vectors: &[Vector],
+        nlist: usize,
+        metric: DistanceMetric,
+        seed: u64,
+        mut progress: impl FnMut(usize, usize),
+    ) -> Result<Self, ZyphyrError> {
+        let dimensions = vectors.first().map(|v| v.dim());
+        if let Some(dims) = dimensions {
+            for v in vectors {
+                if v.dim() != dims {
+                    return Err(ZyphyrError::InvalidDimension { expected: dims, got: v.dim() });
+                }
+            }
+        }
+        
+        let total = TRAIN_ITERATIONS + 1;
+        if vectors.is_empty() {
+            progress(total, total);
+            return Ok(IvfIndex {
+                vectors: Vec::new(),
+                centroids: Vec::new(),
+                lists: Vec::new(),
+                dimensions,
+                metric,
+            });
+        }
+        
+        let points: Vec<&[f32]> = vectors.iter().map(|v| v.data()).collect();
+        let mut rng = StdRng::seed_from_u64(seed);
+        let clustering = kmeans_with_progress(&points, nlist, TRAIN_ITERATIONS, &metric, &mut rng, |done| {
+            progress(done, total);
+        })?;
+        
+        let mut lists = vec![Vec::new(); clustering.centroids.len()];
+        for (i, &cluster) in clustering.assignments.iter().enumerate() {
+            lists[cluster].push(i);
+        }
+        progress(total, total);
+        
+        Ok(IvfIndex {
+            vectors: vectors.to_vec(),
+            centroids: clustering.centroids,
+            lists,
+            dimensions,
+            metric,
+        })
+    }
+
+    /// Return the `k` nearest vectors found in the `nprobe` closest inverted lists
+    pub fn search(
+        &self,
+        query: &Vector,
+        k: usize,
+        nprobe: usize,
+    ) -> Result<Vec<(String, f32)>, ZyphyrError> {
+        if let Some(dims) = self.dimensions
+            && query.dim() != dims
+        {
+            return Err(ZyphyrError::InvalidDimension { expected: dims, got: query.dim() });
+        }
+        
+        // Rank the coarse centroids and keep the closest `nprobe`
+        let mut probes = self.centroids
+            .iter()
+            .enumerate()
+            .map(|(i, centroid)| Ok((i, self.metric.compute_slices(query.data(), centroid)?)))
+            .collect::<Result<Vec<_>, ZyphyrError>>()?;
+        probes.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+        
+        let mut results = Vec::new();
+        for &(list, _) in probes.iter().take(nprobe) {
+            for &index in &self.lists[list] {
+                let v = &self.vectors[index];
+                results.push((v.id().to_string(), self.metric.compute(query, v)?));
+            }
+        }
+        
+        results.sort_by(|a, b| {
+            a.1.partial_cmp(&b.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        results.truncate(k);
+        Ok(results)
+    }
+
+    /// Index of the inverted list a vector would be assigned to
+    pub fn assign(&self, vector: &Vector) -> Result<usize, ZyphyrError> {
+        if self.centroids.is_empty() {
+            return Err(ZyphyrError::Other("IVF index has no trained centroids".to_string()));
+        }
+        Ok(nearest_centroid(vector.data(), &self.centroids, &self.metric)?.0)
+    }
+
+    pub fn nlist(&self) -> usize {
+        self.centroids.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.vectors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vectors.is_empty()
+    }
+
+    pub fn metric(&self) -> DistanceMetric {
+        self.metric.clone()
+    }
+}
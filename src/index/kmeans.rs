@@ -0,0 +1,153 @@
+use crate::{DistanceMetric, ZyphyrError};
+use rand::Rng;
+
+/// Result of a k-means run: one centroid per cluster and the cluster of each point
+pub(crate) struct KMeans {
+    pub centroids: Vec<Vec<f32>>,
+    pub assignments: Vec<usize>,
+}
+
+/// Lloyd's algorithm with k-means++ seeding. Clusters that end up empty are
+/// re-seeded from the point farthest from its current centroid.
+pub(crate) fn kmeans<R: Rng>(
+    points: &[&[f32]],
+    k: usize,
+    max_iters: usize,
+    metric: &DistanceMetric,
+    rng: &mut R,
+) -> Result<KMeans, ZyphyrError> {
+    kmeans_with_progress(points, k, max_iters, metric, rng, |_| {})
+}
+
+/// [`kmeans`] calling `on_iteration` with the number of Lloyd iterations completed
+/// after each one. It isn't called for the final iteration that detects convergence.
+pub(crate) fn kmeans_with_progress<R: Rng>(
+    points: &[&[f32]],
+    k: usize,
+    max_iters: usize,
+    metric: &DistanceMetric,
+    rng: &mut R,
+    mut on_iteration: impl FnMut(usize),
+) -> Result<KMeans, ZyphyrError> {
+    if k == 0 || k > points.len() {
+        return Err(ZyphyrError::Other(format!(
+            "k must be between 1 and the number of points ({}), got {}",
+            points.len(),
+            k
+        )));
+    }
+    let dim = points[0].len();
+    
+    let mut centroids = kmeans_plus_plus(points, k, metric, rng)?;
+    let mut assignments = vec![usize::MAX; points.len()];
+    let mut distances = vec![0.0f32; points.len()];
+    
+    for iteration in 0..max_iters.max(1) {
+        // Assignment step
+        let mut changed = false;
+        for (i, point) in points.iter().enumerate() {
+            let (nearest, distance) = nearest_centroid(point, &centroids, metric)?;
+            distances[i] = distance;
+            if assignments[i] != nearest {
+                assignments[i] = nearest;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+        
+        // Update step: move each centroid to the mean of its members
+        let mut sums = vec![vec![0.0f32; dim]; k];
+        let mut counts = vec![0usize; k];
+        for (point, &cluster) in points.iter().zip(assignments.iter()) {
+            counts[cluster] += 1;
+            for (sum, &x) in sums[cluster].iter_mut().zip(point.iter()) {
+                *sum += x;
+            }
+        }
+        
+        for cluster in 0..k {
+            if counts[cluster] > 0 {
+                let count = counts[cluster] as f32;
+                centroids[cluster] = sums[cluster].iter().map(|s| s / count).collect();
+                continue;
+            }
+            
+            // Empty cluster: steal the point that is currently worst served
+            let farthest = distances
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| counts[assignments[*i]] > 1)
+                .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(std::cmp::Ordering::Equal))
+                .map(|(i, _)| i);
+            if let Some(point) = farthest {
+                counts[assignments[point]] -= 1;
+                counts[cluster] = 1;
+                assignments[point] = cluster;
+                distances[point] = 0.0;
+                centroids[cluster] = points[point].to_vec();
+            }
+        }
+        on_iteration(iteration + 1);
+    }
+    
+    Ok(KMeans { centroids, assignments })
+}
+
+/// Index and distance of the centroid closest to `point`
+pub(crate) fn nearest_centroid(
+    point: &[f32],
+    centroids: &[Vec<f32>],
+    metric: &DistanceMetric,
+) -> Result<(usize, f32), ZyphyrError> {
+    let mut best = (0, f32::INFINITY);
+    for (i, centroid) in centroids.iter().enumerate() {
+        let distance = metric.compute_slices(point, centroid)?;
+        if distance < best.1 {
+            best = (i, distance);
+        }
+    }
+    Ok(best)
+}
+
+// k-means++: pick each new centroid with probability proportional to its squared
+// distance from the nearest centroid chosen so far
+fn kmeans_plus_plus<R: Rng>(
+    points: &[&[f32]],
+    k: usize,
+    metric: &DistanceMetric,
+    rng: &mut R,
+) -> Result<Vec<Vec<f32>>, ZyphyrError> {
+    let mut centroids = Vec::with_capacity(k);
+    centroids.push(points[rng.random_range(0..points.len())].to_vec());
+    
+    let mut weights = vec![f32::INFINITY; points.len()];
+    while centroids.len() < k {
+        let latest = centroids.last().unwrap();
+        for (weight, point) in weights.iter_mut().zip(points.iter()) {
+            let distance = metric.compute_slices(point, latest)?.max(0.0);
+            *weight = weight.min(distance * distance);
+        }
+        
+        let total: f32 = weights.iter().sum();
+        let chosen = if total > 0.0 && total.is_finite() {
+            let mut target = rng.random_range(0.0..total);
+            let mut chosen = points.len() - 1;
+            for (i, &weight) in weights.iter().enumerate() {
+                if target < weight {
+                    chosen = i;
+                    break;
+                }
+                target -= weight;
+            }
+            chosen
+        } else {
+            // Every point coincides with a centroid; fall back to uniform sampling
+            rng.random_range(0..points.len())
+        };
+        centroids.push(points[chosen].to_vec());
+    }
+    
+    Ok(centroids)
+}
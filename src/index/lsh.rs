@@ -0,0 +1,171 @@
+use crate::{Vector, ZyphyrError, DistanceMetric};
+use super::hnsw::progress_interval;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use std::collections::HashMap;
+
+/// One hash family: `num_bits` random hyperplanes and the buckets they induce
+struct HashTable {
+    hyperplanes: Vec<Vec<f32>>,
+    buckets: HashMap<u64, Vec<usize>>,  // Signature -> member vector indices
+}
+
+impl HashTable {
+    fn signature(&self, data: &[f32], mean: &[f32]) -> u64 {
+        let mut signature = 0u64;
+        for (bit, plane) in self.hyperplanes.iter().enumerate() {
+            let side: f32 = plane
+                .iter()
+                .zip(data.iter().zip(mean))
+                .map(|(p, (x, m))| p * (x - m))
+                .sum();
+            if side >= 0.0 {
+                signature |= 1 << bit;
+            }
+        }
+        signature
+    }
+}
+
+/// Locality-sensitive hashing index using random hyperplane (sign bit) signatures.
+/// Each of the `num_tables` tables buckets vectors by which side of `num_bits`
+/// hyperplanes they fall on; queries union their matching buckets across tables
+/// and rerank those candidates with the exact metric.
+pub struct LshIndex {
+    vectors: Vec<Vector>,
+    tables: Vec<HashTable>,
+    mean: Vec<f32>,  // Hyperplanes pass through the data mean rather than the origin
+    dimensions: Option<usize>,
+    metric: DistanceMetric,
+}
+
+impl LshIndex {
+    /// Hash `vectors` into `num_tables` tables of `num_bits`-bit signatures.
+    /// `num_bits` must be between 1 and 64. `seed` drives the hyperplanes, so the
+    /// same seed and input always produce the same buckets.
+    pub fn build(
+        vectors: &[Vector],
+        num_tables: usize,
+        num_bits: usize,
+        metric: DistanceMetric,
+        seed: u64,
+    ) -> Result<Self, ZyphyrError> {
+        Self::build_with_progress(vectors, num_tables, num_bits, metric, seed, |_, _| {})
+    }
+
+    /// Like [`LshIndex::build`], reporting `(tables built, num_tables)` to `progress`
+    /// as each hash table is filled. The callback follows the same contract as
+    /// [`crate::HnswIndex::build_with_progress`]: it runs on the building thread
+    /// and is never called concurrently.
+    pub fn build_with_progress(
+        vectors: &[Vector],
+        num_tables: usize,
+        num_bits: usize,
+        metric: DistanceMetric,
+        seed: u64,
+        mut progress: impl FnMut(usize, usize),
+    ) -> Result<Self, ZyphyrError> {
+        if num_tables == 0 {
+            return Err(ZyphyrError::Other("LSH needs at least one hash table".to_string()));
+        }
+        if num_bits == 0 || num_bits > 64 {
+            return Err(ZyphyrError::Other(format!("LSH num_bits must be in 1..=64, got {}", num_bits)));
+        }
+        
+        let dimensions = vectors.first().map(|v| v.dim());
+        let dim = dimensions.unwrap_or(0);
+        for v in vectors {
+            if v.dim() != dim {
+                return Err(ZyphyrError::InvalidDimension { expected: dim, got: v.dim() });
+            }
+        }
+        
+        let mut mean = vec![0.0f32; dim];
+        for v in vectors {
+            for (m, &x) in mean.iter_mut().zip(v.data()) {
+                *m += x;
+            }
+        }
+        if !vectors.is_empty() {
+            let count = vectors.len() as f32;
+            mean.iter_mut().for_each(|m| *m /= count);
+        }
+        
+        let mut rng = StdRng::seed_from_u64(seed);
+        let interval = progress_interval(num_tables);
+        let mut tables = Vec::with_capacity(num_tables);
+        for built in 1..=num_tables {
+            let hyperplanes = (0..num_bits)
+                .map(|_| (0..dim).map(|_| rng.random_range(-1.0..1.0)).collect())
+                .collect();
+            let mut table = HashTable { hyperplanes, buckets: HashMap::new() };
+            for (index, v) in vectors.iter().enumerate() {
+                let signature = table.signature(v.data(), &mean);
+                table.buckets.entry(signature).or_default().push(index);
+            }
+            tables.push(table);
+            if built % interval == 0 || built == num_tables {
+                progress(built, num_tables);
+            }
+        }
+        
+        Ok(LshIndex {
+            vectors: vectors.to_vec(),
+            tables,
+            mean,
+            dimensions,
+            metric,
+        })
+    }
+
+    /// Return up to `k` nearest vectors among those sharing a bucket with `query`
+    /// in at least one table
+    pub fn query(&self, query: &Vector, k: usize) -> Result<Vec<(String, f32)>, ZyphyrError> {
+        if let Some(dims) = self.dimensions
+            && query.dim() != dims
+        {
+            return Err(ZyphyrError::InvalidDimension { expected: dims, got: query.dim() });
+        }
+        
+        let mut seen = vec![false; self.vectors.len()];
+        let mut results = Vec::new();
+        for table in &self.tables {
+            let signature = table.signature(query.data(), &self.mean);
+            let Some(bucket) = table.buckets.get(&signature) else {
+                continue;
+            };
+            for &index in bucket {
+                if seen[index] {
+                    continue;
+                }
+                seen[index] = true;
+                let v = &self.vectors[index];
+                results.push((v.id().to_string(), self.metric.compute(query, v)?));
+            }
+        }
+        
+        results.sort_by(|a, b| {
+            a.1.partial_cmp(&b.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        results.truncate(k);
+        Ok(results)
+    }
+
+    pub fn num_tables(&self) -> usize {
+        self.tables.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.vectors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vectors.is_empty()
+    }
+
+    pub fn metric(&self) -> DistanceMetric {
+        self.metric.clone()
+    }
+}
@@ -0,0 +1,9 @@
+pub use self::hnsw::HnswIndex;
+pub use self::hnsw_pq::HnswPqIndex;
+pub use self::ivf::IvfIndex;
+pub use self::lsh::LshIndex;
+mod hnsw;
+mod hnsw_pq;
+mod ivf;
+mod lsh;
+pub(crate) mod kmeans;
\ No newline at end of file
@@ -0,0 +1,39 @@
+use crate::{Vector, VectorCollection, ZyphyrError};
+use std::io::{BufRead, BufReader, Read};
+
+impl VectorCollection {
+    /// Load vectors from CSV rows of the form `id,x0,x1,...`.
+    /// Blank lines are skipped; rows must all have the same number of values.
+    pub fn from_csv_reader<R: Read>(reader: R, has_header: bool) -> Result<Self, ZyphyrError> {
+        let mut collection = VectorCollection::new();
+        
+        for (line_number, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            if (has_header && line_number == 0) || line.trim().is_empty() {
+                continue;
+            }
+            
+            let mut cells = line.split(',').map(str::trim);
+            let id = cells.next().unwrap_or_default().trim_matches('"');
+            if id.is_empty() {
+                return Err(ZyphyrError::Other(format!("Missing id on CSV line {}", line_number + 1)));
+            }
+            
+            let data = cells
+                .map(|cell| {
+                    cell.parse::<f32>().map_err(|_| {
+                        ZyphyrError::Other(format!(
+                            "Invalid number {:?} on CSV line {}",
+                            cell,
+                            line_number + 1
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<f32>, ZyphyrError>>()?;
+            
+            collection.insert(Vector::new(id, data)?)?;
+        }
+        
+        Ok(collection)
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{Vector, ZyphyrError, DistanceMetric};
+use memmap2::Mmap;
+use std::cmp::Ordering;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Magic bytes identifying a memory-mappable vector file
+const MMAP_MAGIC: &[u8; 4] = b"ZYMM";
+
+/// Header layout: magic, u64 count, u64 dimension (all little-endian)
+const HEADER_LEN: usize = 4 + 8 + 8;
+
+/// Read-only collection over a memory-mapped file of contiguous little-endian f32 rows.
+/// Rows are read straight out of the mapping, so the file can be larger than RAM.
+pub struct MmapCollection {
+    mmap: Mmap,
+    count: usize,
+    dim: usize,
+}
+
+impl MmapCollection {
+    /// Write `vectors` in the layout expected by [`MmapCollection::open`]
+    pub fn write_file<P: AsRef<Path>>(path: P, vectors: &[Vector]) -> Result<(), ZyphyrError> {
+        let dim = vectors.first().map_or(0, |v| v.dim());
+        let mut writer = BufWriter::new(File::create(path)?);
+        
+        writer.write_all(MMAP_MAGIC)?;
+        writer.write_all(&(vectors.len() as u64).to_le_bytes())?;
+        writer.write_all(&(dim as u64).to_le_bytes())?;
+        for v in vectors {
+            if v.dim() != dim {
+                return Err(ZyphyrError::InvalidDimension { expected: dim, got: v.dim() });
+            }
+            for value in v.data() {
+                writer.write_all(&value.to_le_bytes())?;
+            }
+        }
+        
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Map `path` and validate its header against the file length
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, ZyphyrError> {
+        if cfg!(target_endian = "big") {
+            return Err(ZyphyrError::Other("MmapCollection requires a little-endian host".to_string()));
+        }
+        
+        let file = File::open(path)?;
+        // Safety: the mapping is read-only; callers must not truncate the file while it is open
+        let mmap = unsafe { Mmap::map(&file)? };
+        
+        if mmap.len() < HEADER_LEN || &mmap[..4] != MMAP_MAGIC {
+            return Err(ZyphyrError::Corrupt("invalid file header".to_string()));
+        }
+        let count = u64::from_le_bytes(mmap[4..12].try_into().unwrap()) as usize;
+        let dim = u64::from_le_bytes(mmap[12..20].try_into().unwrap()) as usize;
+        
+        let expected = count
+            .checked_mul(dim)
+            .and_then(|n| n.checked_mul(4))
+            .and_then(|n| n.checked_add(HEADER_LEN));
+        if expected != Some(mmap.len()) {
+            return Err(ZyphyrError::Corrupt(format!(
+                "file is {} bytes but header describes {} rows of dimension {}",
+                mmap.len(),
+                count,
+                dim
+            )));
+        }
+        if count > 0 && dim == 0 {
+            return Err(ZyphyrError::Corrupt("non-empty collection with zero dimension".to_string()));
+        }
+        
+        Ok(MmapCollection { mmap, count, dim })
+    }
+
+    /// Borrow row `i` directly from the mapping.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i >= self.len()`.
+    pub fn get_row(&self, i: usize) -> &[f32] {
+        assert!(i < self.count, "row {} out of range for {} rows", i, self.count);
+        &self.rows()[i * self.dim..(i + 1) * self.dim]
+    }
+
+    /// Brute-force top-`k` search returning `(row index, distance)` pairs,
+    /// streaming over the mapped rows without copying them
+    pub fn search(
+        &self,
+        query: &Vector,
+        k: usize,
+        metric: DistanceMetric,
+    ) -> Result<Vec<(usize, f32)>, ZyphyrError> {
+        if self.count > 0 && query.dim() != self.dim {
+            return Err(ZyphyrError::InvalidDimension { expected: self.dim, got: query.dim() });
+        }
+        
+        let mut results = Vec::with_capacity(self.count);
+        for (i, row) in self.rows().chunks_exact(self.dim.max(1)).enumerate() {
+            results.push((i, metric.compute_slices(query.data(), row)?));
+        }
+        results.sort_by(|a, b| {
+            a.1.partial_cmp(&b.1)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        results.truncate(k);
+        Ok(results)
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    // All rows as one f32 slice over the mapped bytes after the header
+    fn rows(&self) -> &[f32] {
+        let bytes = &self.mmap[HEADER_LEN..];
+        // Mappings are page aligned and the header is a multiple of 4 bytes,
+        // so the row data is always suitably aligned for f32
+        debug_assert!(bytes.as_ptr().cast::<f32>().is_aligned());
+        // Safety: `open` verified the length covers exactly count * dim f32 values,
+        // the data is aligned, and every bit pattern is a valid f32
+        unsafe { std::slice::from_raw_parts(bytes.as_ptr().cast::<f32>(), self.count * self.dim) }
+    }
+}
@@ -0,0 +1,4 @@
+pub use self::mmap::MmapCollection;
+
+mod csv;
+mod mmap;
@@ -1,7 +1,11 @@
 //! Zyphyr - High-performance vector database with HNSW indexing
 
 mod error;
+mod eval;
 mod vector;
+mod index;
+mod io;
+mod transform;
 mod utils;
 
 #[cfg(test)]
@@ -9,45 +13,26 @@ mod tests;
 
 // Re-export primary types
 pub use error::ZyphyrError;
-pub use vector::{Vector, VectorCollection, DistanceMetric};
-pub use utils::alignment::{SIMD_ALIGNMENT, is_aligned};
+pub use vector::{Vector, VectorCollection, VectorCollectionBuilder, ConcurrentCollection, ShardedCollection, DistanceMetric, CustomDistanceFn, Metadata, VectorElement};
+pub use vector::{SearchOptions, SearchScratch, TieBreak, CollectionStats, merge_search_results};
+pub use vector::{Distance, Euclidean, Cosine, DotProduct};
+pub use vector::{QuantizedVector, BinaryVector, quantized_euclidean, hamming_distance};
+pub use vector::{SparseVector, SparseCollection};
+pub use vector::{ProductQuantizer, PqCode, DistanceTable};
+#[cfg(feature = "f16")]
+pub use vector::HalfVector;
+pub use index::{HnswIndex, HnswPqIndex, IvfIndex, LshIndex};
+pub use transform::{PcaProjector, RandomProjector};
+pub use io::MmapCollection;
+pub use eval::{recall_at_k, mean_recall_at_k};
+pub use utils::alignment::{SIMD_ALIGNMENT, is_aligned, get_simd_width};
+pub use utils::simd::{SimdCapabilities, detect_simd};
 
 /// Version of the library
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
-/// Returns information about SIMD support on the current platform
+/// Returns information about SIMD support on the current platform.
+/// See [`detect_simd`] for a structured version.
 pub fn simd_support_info() -> String {
-    #[cfg(target_arch = "x86_64")]
-    {
-        use std::arch::x86_64::{__cpuid, __cpuid_count};
-        
-        let mut info = String::new();
-        unsafe {
-            let cpuid = __cpuid(1);
-            
-            if (cpuid.ecx >> 28) & 1 != 0 {
-                info.push_str("AVX supported\n");
-            }
-            
-            let cpuid7 = __cpuid_count(7, 0);
-            if (cpuid7.ebx >> 5) & 1 != 0 {
-                info.push_str("AVX2 supported\n");
-            }
-            
-            if (cpuid7.ebx >> 16) & 1 != 0 {
-                info.push_str("AVX-512 supported\n");
-            }
-        }
-        
-        if info.is_empty() {
-            "No advanced SIMD features detected".to_string()
-        } else {
-            info
-        }
-    }
-    
-    #[cfg(not(target_arch = "x86_64"))]
-    {
-        "SIMD support detection not implemented for this architecture".to_string()
-    }
-}
\ No newline at end of file
+    detect_simd().to_string()
+}
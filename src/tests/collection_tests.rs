@@ -0,0 +1,1510 @@
+#[cfg(test)]
+mod tests {
+    use crate::{SearchOptions, SearchScratch, TieBreak, merge_search_results};
+    use crate::{Vector, VectorCollection, ConcurrentCollection, ShardedCollection, DistanceMetric, ZyphyrError};
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn random_collection(count: usize, dim: usize, seed: u64) -> VectorCollection {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut collection = VectorCollection::new();
+        for i in 0..count {
+            let data: Vec<f32> = (0..dim).map(|_| rng.random_range(-1.0..1.0)).collect();
+            collection.insert(Vector::new(format!("v{}", i), data).unwrap()).unwrap();
+        }
+        collection
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("zyphyr_{}_{}.bin", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_save_load_round_trip() {
+        let collection = random_collection(100, 16, 3);
+        let path = temp_path("round_trip");
+        
+        collection.save(&path).unwrap();
+        let loaded = VectorCollection::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        
+        assert_eq!(loaded.len(), collection.len());
+        assert_eq!(loaded.get("v42").unwrap().data(), collection.get("v42").unwrap().data());
+        
+        let query = Vector::new("query", vec![0.5; 16]).unwrap();
+        for metric in [DistanceMetric::Euclidean, DistanceMetric::Cosine, DistanceMetric::DotProduct] {
+            let original = collection.search(&query, 10, metric.clone()).unwrap();
+            let restored = loaded.search(&query, 10, metric).unwrap();
+            assert_eq!(original, restored);
+        }
+    }
+
+    #[test]
+    fn test_save_load_drops_metadata() {
+        let mut collection = VectorCollection::new();
+        let metadata = HashMap::from([("source".to_string(), "a.txt".to_string())]);
+        collection.insert(Vector::new("a", vec![1.0, 2.0]).unwrap().with_metadata(metadata)).unwrap();
+        
+        let path = temp_path("metadata");
+        collection.save(&path).unwrap();
+        let loaded = VectorCollection::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        
+        // The binary format keeps ids and data only
+        assert!(collection.get("a").unwrap().metadata().is_some());
+        assert_eq!(loaded.get("a").unwrap().data(), &[1.0, 2.0]);
+        assert!(loaded.get("a").unwrap().metadata().is_none());
+    }
+
+    #[test]
+    fn test_save_load_empty_collection() {
+        let path = temp_path("empty");
+        VectorCollection::new().save(&path).unwrap();
+        let loaded = VectorCollection::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(loaded.is_empty());
+    }
+
+    #[test]
+    fn test_load_corrupt_file() {
+        let path = temp_path("corrupt");
+        
+        std::fs::write(&path, b"NOPE").unwrap();
+        assert!(matches!(VectorCollection::load(&path), Err(ZyphyrError::Corrupt(_))));
+        
+        // Valid header followed by a truncated body
+        let collection = random_collection(5, 4, 1);
+        collection.save(&path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &bytes[..bytes.len() - 3]).unwrap();
+        assert!(matches!(VectorCollection::load(&path), Err(ZyphyrError::Corrupt(_))));
+        
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(VectorCollection::load(&path), Err(ZyphyrError::Io(_))));
+    }
+
+    #[test]
+    fn test_load_rejects_oversized_header() {
+        let path = temp_path("oversized");
+        let header = |dim: u64, count: u64| {
+            let mut bytes = b"ZYPH".to_vec();
+            bytes.extend_from_slice(&dim.to_le_bytes());
+            bytes.extend_from_slice(&count.to_le_bytes());
+            bytes
+        };
+        
+        // A count the file can't hold must fail before anything is allocated for it
+        std::fs::write(&path, header(4, u64::MAX)).unwrap();
+        assert!(matches!(VectorCollection::load(&path), Err(ZyphyrError::Corrupt(_))));
+        
+        std::fs::write(&path, header(u64::MAX, 1)).unwrap();
+        assert!(matches!(VectorCollection::load(&path), Err(ZyphyrError::Corrupt(_))));
+        
+        // One record whose id length claims far more bytes than remain
+        let mut bytes = header(1, 1);
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        bytes.extend_from_slice(&1.0f32.to_le_bytes());
+        std::fs::write(&path, &bytes).unwrap();
+        assert!(matches!(VectorCollection::load(&path), Err(ZyphyrError::Corrupt(_))));
+        
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_search_with_metadata() {
+        let mut collection = VectorCollection::new();
+        let mut metadata = HashMap::new();
+        metadata.insert("source".to_string(), "news".to_string());
+        collection.insert(Vector::new("v1", vec![1.0, 0.0]).unwrap().with_metadata(metadata)).unwrap();
+        collection.insert(Vector::new("v2", vec![0.0, 1.0]).unwrap()).unwrap();
+        
+        let query = Vector::new("query", vec![1.0, 0.1]).unwrap();
+        let results = collection.search_with_metadata(&query, 2, DistanceMetric::Euclidean).unwrap();
+        
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, "v1");
+        assert_eq!(results[0].2.as_ref().unwrap().get("source").unwrap(), "news");
+        assert_eq!(results[1].0, "v2");
+        assert!(results[1].2.is_none());
+    }
+
+    #[test]
+    fn test_search_filtered_by_metadata() {
+        let mut collection = VectorCollection::new();
+        for i in 0..10 {
+            let mut metadata = HashMap::new();
+            let category = if i % 2 == 0 { "news" } else { "blog" };
+            metadata.insert("category".to_string(), category.to_string());
+            let v = Vector::new(format!("v{}", i), vec![i as f32, 0.0]).unwrap().with_metadata(metadata);
+            collection.insert(v).unwrap();
+        }
+        
+        let is_news = |v: &Vector| {
+            v.metadata().and_then(|m| m.get("category")).is_some_and(|c| c == "news")
+        };
+        let query = Vector::new("query", vec![1.0, 0.0]).unwrap();
+        
+        let results = collection.search_filtered(&query, 3, DistanceMetric::Euclidean, is_news).unwrap();
+        let ids: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["v0", "v2", "v4"]);
+        
+        // Asking for more than pass the filter returns only the filtered set
+        let results = collection.search_filtered(&query, 8, DistanceMetric::Euclidean, is_news).unwrap();
+        assert_eq!(results.len(), 5);
+        for (id, _) in &results {
+            let index: usize = id[1..].parse().unwrap();
+            assert_eq!(index % 2, 0, "excluded id {} returned", id);
+        }
+    }
+
+    #[test]
+    fn test_upsert() {
+        let mut collection = VectorCollection::new();
+        collection.insert(Vector::new("v1", vec![1.0, 2.0]).unwrap()).unwrap();
+        collection.insert(Vector::new("v2", vec![3.0, 4.0]).unwrap()).unwrap();
+        
+        let inserted = collection.upsert(Vector::new("v1", vec![5.0, 6.0]).unwrap()).unwrap();
+        assert!(!inserted);
+        assert_eq!(collection.len(), 2);
+        assert_eq!(collection.get("v1").unwrap().data(), &[5.0, 6.0]);
+        
+        let inserted = collection.upsert(Vector::new("v3", vec![7.0, 8.0]).unwrap()).unwrap();
+        assert!(inserted);
+        assert_eq!(collection.len(), 3);
+        
+        // Dimension consistency is still enforced on update
+        let result = collection.upsert(Vector::new("v1", vec![1.0, 2.0, 3.0]).unwrap());
+        assert!(matches!(result, Err(ZyphyrError::InvalidDimension { expected: 2, got: 3 })));
+        assert_eq!(collection.get("v1").unwrap().data(), &[5.0, 6.0]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_search_parallel_matches_serial() {
+        let collection = random_collection(2000, 32, 11);
+        let query = Vector::new("query", vec![0.1; 32]).unwrap();
+        
+        for k in [0, 1, 10, 100, 5000] {
+            let serial = collection.search(&query, k, DistanceMetric::Euclidean).unwrap();
+            let parallel = collection.search_parallel(&query, k, DistanceMetric::Euclidean).unwrap();
+            assert_eq!(serial, parallel);
+        }
+        
+        // Equidistant vectors must come back in id order from both paths
+        let mut ties = VectorCollection::new();
+        for id in ["d", "b", "c", "a"] {
+            ties.insert(Vector::new(id, vec![1.0, 0.0]).unwrap()).unwrap();
+        }
+        let query = Vector::new("query", vec![0.0, 0.0]).unwrap();
+        let serial = ties.search(&query, 3, DistanceMetric::Euclidean).unwrap();
+        let parallel = ties.search_parallel(&query, 3, DistanceMetric::Euclidean).unwrap();
+        assert_eq!(serial, parallel);
+        let ids: Vec<&str> = parallel.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+        
+        // Same cosine fast path and dimension check as the serial search
+        let mut normalized = VectorCollection::builder().normalize_on_insert(true).build();
+        for v in random_collection(300, 8, 12).iter() {
+            normalized.insert(v.clone()).unwrap();
+        }
+        let query = Vector::new("query", vec![0.3; 8]).unwrap();
+        assert_eq!(
+            normalized.search(&query, 10, DistanceMetric::Cosine).unwrap(),
+            normalized.search_parallel(&query, 10, DistanceMetric::Cosine).unwrap()
+        );
+        let short = Vector::new("short", vec![0.3; 4]).unwrap();
+        assert!(matches!(
+            normalized.search_parallel(&short, 10, DistanceMetric::Cosine),
+            Err(ZyphyrError::InvalidDimension { expected: 8, got: 4 })
+        ));
+    }
+
+    #[test]
+    fn test_try_get_and_try_remove() {
+        let mut collection = VectorCollection::new();
+        collection.insert(Vector::new("v1", vec![1.0, 2.0]).unwrap()).unwrap();
+        
+        assert_eq!(collection.try_get("v1").unwrap().id(), "v1");
+        assert!(matches!(collection.try_get("missing"), Err(ZyphyrError::IdNotFound(id)) if id == "missing"));
+        
+        assert!(matches!(collection.try_remove("missing"), Err(ZyphyrError::IdNotFound(id)) if id == "missing"));
+        assert_eq!(collection.len(), 1);
+        
+        let removed = collection.try_remove("v1").unwrap();
+        assert_eq!(removed.id(), "v1");
+        assert!(collection.is_empty());
+        assert!(matches!(collection.try_get("v1"), Err(ZyphyrError::IdNotFound(_))));
+    }
+
+    #[test]
+    fn test_iter_and_ids() {
+        let collection = random_collection(5, 3, 5);
+        assert_eq!(collection.iter().count(), 5);
+        
+        let ids: Vec<&str> = collection.ids().collect();
+        assert_eq!(ids, vec!["v0", "v1", "v2", "v3", "v4"]);
+        for (vector, id) in collection.iter().zip(collection.ids()) {
+            assert_eq!(vector.id(), id);
+            assert_eq!(vector.dim(), 3);
+        }
+    }
+
+    #[test]
+    fn test_range_search() {
+        let mut collection = VectorCollection::new();
+        collection.insert(Vector::new("far", vec![10.0, 0.0]).unwrap()).unwrap();
+        collection.insert(Vector::new("near", vec![1.0, 0.0]).unwrap()).unwrap();
+        collection.insert(Vector::new("nearest", vec![0.5, 0.0]).unwrap()).unwrap();
+        
+        let query = Vector::new("query", vec![0.0, 0.0]).unwrap();
+        let results = collection.range_search(&query, 1.0, DistanceMetric::Euclidean).unwrap();
+        let ids: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["nearest", "near"]);
+        
+        // Nothing in range is an empty result, not an error
+        let results = collection.range_search(&query, 0.1, DistanceMetric::Euclidean).unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_kmeans_separates_blobs() {
+        let mut rng = StdRng::seed_from_u64(17);
+        let mut collection = VectorCollection::new();
+        for i in 0..40 {
+            let (prefix, center) = if i % 2 == 0 { ("a", -5.0) } else { ("b", 5.0) };
+            let data: Vec<f32> = (0..4).map(|_| center + rng.random_range(-1.0..1.0)).collect();
+            collection.insert(Vector::new(format!("{}{}", prefix, i), data).unwrap()).unwrap();
+        }
+        
+        let clusters = collection.kmeans(2, 50, DistanceMetric::Euclidean, 42).unwrap();
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].len() + clusters[1].len(), 40);
+        for cluster in &clusters {
+            assert_eq!(cluster.len(), 20);
+            let prefix = &cluster[0][..1];
+            assert!(cluster.iter().all(|id| id.starts_with(prefix)));
+        }
+        
+        assert!(collection.kmeans(0, 10, DistanceMetric::Euclidean, 42).is_err());
+        assert!(collection.kmeans(41, 10, DistanceMetric::Euclidean, 42).is_err());
+    }
+
+    #[test]
+    fn test_kmeans_reseeds_empty_clusters() {
+        // Duplicate points make k-means++ pick coincident seeds, so one cluster
+        // starts empty and has to be re-seeded
+        let mut collection = VectorCollection::new();
+        for i in 0..6 {
+            collection.insert(Vector::new(format!("same{}", i), vec![1.0, 1.0]).unwrap()).unwrap();
+        }
+        collection.insert(Vector::new("outlier", vec![9.0, 9.0]).unwrap()).unwrap();
+        
+        let clusters = collection.kmeans(3, 20, DistanceMetric::Euclidean, 42).unwrap();
+        assert_eq!(clusters.len(), 3);
+        assert!(clusters.iter().all(|c| !c.is_empty()));
+        assert_eq!(clusters.iter().map(|c| c.len()).sum::<usize>(), 7);
+    }
+
+    #[test]
+    fn test_builder_pins_dimension() {
+        let mut collection = VectorCollection::builder()
+            .dimension(3)
+            .capacity(16)
+            .build();
+        assert_eq!(collection.dimensions(), Some(3));
+        
+        // The very first insert is validated against the pinned dimension
+        let result = collection.insert(Vector::new("v1", vec![1.0, 2.0]).unwrap());
+        assert!(matches!(result, Err(ZyphyrError::InvalidDimension { expected: 3, got: 2 })));
+        assert!(collection.is_empty());
+        
+        collection.insert(Vector::new("v1", vec![1.0, 2.0, 3.0]).unwrap()).unwrap();
+        assert_eq!(collection.len(), 1);
+    }
+
+    #[test]
+    fn test_builder_default_metric() {
+        let mut collection = VectorCollection::builder()
+            .default_metric(DistanceMetric::Cosine)
+            .build();
+        assert_eq!(collection.default_metric(), DistanceMetric::Cosine);
+        assert_eq!(VectorCollection::new().default_metric(), DistanceMetric::Euclidean);
+        
+        // Euclidean would prefer "short"; cosine prefers the aligned "long"
+        collection.insert(Vector::new("short", vec![0.5, 0.5]).unwrap()).unwrap();
+        collection.insert(Vector::new("long", vec![10.0, 0.0]).unwrap()).unwrap();
+        let query = Vector::new("query", vec![1.0, 0.0]).unwrap();
+        
+        let results = collection.search_default(&query, 2).unwrap();
+        assert_eq!(results, collection.search(&query, 2, DistanceMetric::Cosine).unwrap());
+        assert_eq!(results[0].0, "long");
+    }
+
+    #[test]
+    fn test_batch_search_matches_individual_searches() {
+        let collection = random_collection(200, 8, 23);
+        let q1 = Vector::new("q1", vec![0.3; 8]).unwrap();
+        let q2 = Vector::new("q2", vec![-0.7; 8]).unwrap();
+        
+        for metric in [DistanceMetric::Euclidean, DistanceMetric::Cosine] {
+            let batch = collection.batch_search(&[&q1, &q2], 5, metric.clone()).unwrap();
+            assert_eq!(batch.len(), 2);
+            assert_eq!(batch[0], collection.search(&q1, 5, metric.clone()).unwrap());
+            assert_eq!(batch[1], collection.search(&q2, 5, metric).unwrap());
+        }
+        
+        assert!(collection.batch_search(&[], 5, DistanceMetric::Euclidean).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_concurrent_searches_are_consistent() {
+        let shared = std::sync::Arc::new(ConcurrentCollection::new(random_collection(500, 16, 26)));
+        let query = Vector::new("query", vec![0.25; 16]).unwrap();
+        let expected = shared.search(&query, 10, DistanceMetric::Euclidean).unwrap();
+        
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let shared = std::sync::Arc::clone(&shared);
+                let query = query.clone();
+                std::thread::spawn(move || {
+                    (0..20)
+                        .map(|_| shared.search(&query, 10, DistanceMetric::Euclidean).unwrap())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        
+        for handle in handles {
+            for results in handle.join().unwrap() {
+                assert_eq!(results, expected);
+            }
+        }
+        
+        // Writes go through the same handle
+        shared.insert(Vector::new("extra", vec![0.25; 16]).unwrap()).unwrap();
+        assert_eq!(shared.len().unwrap(), 501);
+        let results = shared.search(&query, 1, DistanceMetric::Euclidean).unwrap();
+        assert_eq!(results[0].0, "extra");
+        assert!(shared.remove("extra").unwrap().is_some());
+        assert!(!shared.contains("extra").unwrap());
+    }
+
+    #[test]
+    fn test_concurrent_collection_propagates_poison() {
+        let shared = std::sync::Arc::new(ConcurrentCollection::new(random_collection(10, 4, 1)));
+        
+        // A writer that panics mid-update may leave the collection inconsistent
+        let writer = std::sync::Arc::clone(&shared);
+        let result = std::thread::spawn(move || {
+            let mut guard = writer.write().unwrap();
+            guard.remove("v0");
+            panic!("writer failed");
+        })
+        .join();
+        assert!(result.is_err());
+        
+        let query = Vector::new("q", vec![0.0; 4]).unwrap();
+        assert!(matches!(shared.len(), Err(ZyphyrError::Corrupt(_))));
+        assert!(matches!(shared.get("v1"), Err(ZyphyrError::Corrupt(_))));
+        assert!(matches!(shared.search(&query, 3, DistanceMetric::Euclidean), Err(ZyphyrError::Corrupt(_))));
+        assert!(matches!(shared.insert(query.clone()), Err(ZyphyrError::Corrupt(_))));
+        
+        let shared = std::sync::Arc::into_inner(shared).unwrap();
+        assert!(matches!(shared.into_inner(), Err(ZyphyrError::Corrupt(_))));
+    }
+
+    #[test]
+    fn test_centroid() {
+        let mut collection = VectorCollection::new();
+        collection.insert(Vector::new("a", vec![0.0, 0.0]).unwrap()).unwrap();
+        collection.insert(Vector::new("b", vec![2.0, 2.0]).unwrap()).unwrap();
+        collection.insert(Vector::new("c", vec![9.0, 9.0]).unwrap()).unwrap();
+        
+        let centroid = collection.centroid(&["a", "b"]).unwrap();
+        assert_eq!(centroid.id(), "centroid");
+        assert_eq!(centroid.data(), &[1.0, 1.0]);
+        
+        assert!(matches!(collection.centroid(&["a", "missing"]), Err(ZyphyrError::IdNotFound(id)) if id == "missing"));
+        assert!(matches!(collection.centroid(&[]), Err(ZyphyrError::Other(_))));
+    }
+
+    #[test]
+    fn test_remove_stable_preserves_order() {
+        let mut collection = VectorCollection::new();
+        for i in 1..=5 {
+            collection.insert(Vector::new(format!("v{}", i), vec![i as f32, 0.0]).unwrap()).unwrap();
+        }
+        
+        let removed = collection.remove_stable("v2").unwrap();
+        assert_eq!(removed.id(), "v2");
+        assert!(collection.remove_stable("v2").is_none());
+        
+        let ids: Vec<&str> = collection.iter().map(|v| v.id()).collect();
+        assert_eq!(ids, vec!["v1", "v3", "v4", "v5"]);
+        
+        // Lookups still resolve to the shifted positions
+        for i in [1, 3, 4, 5] {
+            let id = format!("v{}", i);
+            assert_eq!(collection.get(&id).unwrap().data()[0], i as f32);
+        }
+    }
+
+    #[test]
+    fn test_soft_remove_skips_search() {
+        let mut collection = VectorCollection::new();
+        for i in 0..5 {
+            collection.insert(Vector::new(format!("v{}", i), vec![i as f32, 0.0]).unwrap()).unwrap();
+        }
+        
+        assert!(collection.soft_remove("v0"));
+        assert!(!collection.soft_remove("v0"));
+        assert!(!collection.contains("v0"));
+        assert!(collection.get("v0").is_none());
+        
+        let query = Vector::new("q", vec![0.0, 0.0]).unwrap();
+        let results = collection.search(&query, 2, DistanceMetric::Euclidean).unwrap();
+        assert_eq!(results[0].0, "v1");
+        assert_eq!(results[1].0, "v2");
+        
+        #[cfg(feature = "rayon")]
+        assert_eq!(collection.search_parallel(&query, 2, DistanceMetric::Euclidean).unwrap(), results);
+        
+        let ids: Vec<&str> = collection.ids().collect();
+        assert_eq!(ids, vec!["v1", "v2", "v3", "v4"]);
+        
+        // The id can be reused while its old slot is still a tombstone
+        collection.insert(Vector::new("v0", vec![10.0, 0.0]).unwrap()).unwrap();
+        assert_eq!(collection.get("v0").unwrap().data()[0], 10.0);
+    }
+
+    #[test]
+    fn test_soft_remove_length_and_compact() {
+        let mut collection = VectorCollection::new();
+        for i in 0..5 {
+            collection.insert(Vector::new(format!("v{}", i), vec![i as f32, 0.0]).unwrap()).unwrap();
+        }
+        
+        collection.soft_remove("v1");
+        collection.soft_remove("v3");
+        assert_eq!(collection.len(), 3);
+        assert_eq!(collection.raw_len(), 5);
+        
+        // Swap-removing next to a tombstone must not resurrect it
+        collection.remove("v4");
+        assert_eq!(collection.len(), 2);
+        assert!(!collection.contains("v3"));
+        
+        collection.compact();
+        assert_eq!(collection.len(), 2);
+        assert_eq!(collection.raw_len(), 2);
+        let ids: Vec<&str> = collection.ids().collect();
+        assert_eq!(ids, vec!["v0", "v2"]);
+        assert_eq!(collection.get("v2").unwrap().data()[0], 2.0);
+        
+        collection.soft_remove("v0");
+        collection.soft_remove("v2");
+        assert!(collection.is_empty());
+        collection.compact();
+        assert_eq!(collection.raw_len(), 0);
+    }
+
+    #[test]
+    fn test_chunks_skip_soft_removed() {
+        let mut collection = VectorCollection::new();
+        for i in 0..10 {
+            collection.insert(Vector::new(format!("v{}", i), vec![i as f32]).unwrap()).unwrap();
+        }
+        collection.soft_remove("v2");
+        collection.soft_remove("v3");
+        collection.soft_remove("v9");
+        
+        let chunks: Vec<Vec<&str>> = collection
+            .chunks(4)
+            .map(|chunk| chunk.iter().map(|v| v.id()).collect())
+            .collect();
+        assert_eq!(chunks, vec![vec!["v0", "v1"], vec!["v4", "v5", "v6", "v7"], vec!["v8"]]);
+        
+        // Once compacted the chunks are full again
+        collection.compact();
+        let sizes: Vec<usize> = collection.chunks(4).map(<[Vector]>::len).collect();
+        assert_eq!(sizes, vec![4, 3]);
+    }
+
+    #[test]
+    fn test_heap_search_matches_full_sort() {
+        let collection = random_collection(500, 16, 21);
+        let mut rng = StdRng::seed_from_u64(22);
+        
+        for metric in [DistanceMetric::Euclidean, DistanceMetric::Cosine, DistanceMetric::DotProduct] {
+            let data: Vec<f32> = (0..16).map(|_| rng.random_range(-1.0..1.0)).collect();
+            let mut query = Vector::new("q", data).unwrap();
+            // Search caches the query norm, so score it through the same cosine path
+            query.norm();
+            
+            let mut expected: Vec<(String, f32)> = collection
+                .iter()
+                .map(|v| (v.id().to_string(), metric.compute(&query, v).unwrap()))
+                .collect();
+            expected.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap().then_with(|| a.0.cmp(&b.0)));
+            
+            for k in [0, 1, 10, 500, 1000] {
+                let results = collection.search(&query, k, metric.clone()).unwrap();
+                let want: Vec<(String, f32)> = expected.iter().take(k).cloned().collect();
+                assert_eq!(results, want, "metric {:?}, k {}", metric, k);
+            }
+        }
+        
+        // Equal distances are ordered by id regardless of insertion order
+        let mut ties = VectorCollection::new();
+        for id in ["c", "a", "d", "b"] {
+            ties.insert(Vector::new(id, vec![1.0, 0.0]).unwrap()).unwrap();
+        }
+        let query = Vector::new("q", vec![0.0, 0.0]).unwrap();
+        let ids: Vec<String> = ties.search(&query, 3, DistanceMetric::Euclidean).unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_normalize_on_insert_cosine_search() {
+        let plain = random_collection(300, 16, 46);
+        let mut normalized = VectorCollection::builder().normalize_on_insert(true).build();
+        assert!(normalized.normalizes_on_insert());
+        for v in plain.iter() {
+            normalized.insert(v.clone()).unwrap();
+        }
+        
+        // Stored data is now unit length
+        let stored = normalized.get("v0").unwrap();
+        let norm: f32 = stored.data().iter().map(|x| x * x).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-5);
+        
+        let mut rng = StdRng::seed_from_u64(47);
+        for _ in 0..10 {
+            let data: Vec<f32> = (0..16).map(|_| rng.random_range(-1.0..1.0)).collect();
+            let query = Vector::new("q", data).unwrap();
+            let expected = plain.search(&query, 20, DistanceMetric::Cosine).unwrap();
+            let results = normalized.search(&query, 20, DistanceMetric::Cosine).unwrap();
+            
+            let expected_ids: Vec<&str> = expected.iter().map(|(id, _)| id.as_str()).collect();
+            let result_ids: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+            assert_eq!(result_ids, expected_ids);
+            for ((_, a), (_, b)) in results.iter().zip(&expected) {
+                assert!((a - b).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_update_data_normalized_cosine() {
+        let mut collection = VectorCollection::builder().normalize_on_insert(true).build();
+        collection.insert(Vector::new("a", vec![1.0, 0.0]).unwrap()).unwrap();
+        collection.insert(Vector::new("b", vec![0.0, 1.0]).unwrap()).unwrap();
+        let query = Vector::new("q", vec![3.0, 3.0]).unwrap();
+        let expected = 1.0 - std::f32::consts::FRAC_1_SQRT_2;
+        
+        // The edit lands on the stored unit vector and is normalized again
+        collection.update_data("a", |data| data[1] = 1.0).unwrap();
+        let stored = collection.get("a").unwrap();
+        assert!(stored.is_normalized());
+        assert!((stored.cached_norm().unwrap() - 1.0).abs() < 1e-6);
+        let results = collection.search(&query, 1, DistanceMetric::Cosine).unwrap();
+        assert_eq!(results[0].0, "a");
+        assert!(results[0].1.abs() < 1e-6);
+        
+        // Editing behind the collection's back leaves "b" at [0, 2], which is no
+        // longer unit length; the scan must not treat cosine as 1 - dot for it
+        collection.get_mut("b").unwrap().update_data(|data| data[1] = 2.0).unwrap();
+        let results = collection.search(&query, 2, DistanceMetric::Cosine).unwrap();
+        assert_eq!(results[1].0, "b");
+        assert!((results[1].1 - expected).abs() < 1e-6);
+        
+        assert!(matches!(collection.update_data("missing", |_| {}), Err(ZyphyrError::IdNotFound(_))));
+        assert!(matches!(collection.update_data("a", |data| data[0] = f32::INFINITY), Err(ZyphyrError::InvalidData(_))));
+    }
+
+    #[test]
+    fn test_distance_matrix() {
+        let mut collection = VectorCollection::new();
+        collection.insert(Vector::new("a", vec![0.0, 0.0]).unwrap()).unwrap();
+        collection.insert(Vector::new("b", vec![3.0, 4.0]).unwrap()).unwrap();
+        collection.insert(Vector::new("c", vec![6.0, 8.0]).unwrap()).unwrap();
+        
+        let queries: Vec<&Vector> = collection.iter().collect();
+        let matrix = collection.distance_matrix(&queries, DistanceMetric::Euclidean).unwrap();
+        assert_eq!(matrix.len(), 3);
+        for i in 0..3 {
+            assert_eq!(matrix[i].len(), 3);
+            assert_eq!(matrix[i][i], 0.0);
+            for j in 0..3 {
+                assert_eq!(matrix[i][j], matrix[j][i]);
+            }
+        }
+        assert_eq!(matrix[0][1], 5.0);
+        assert_eq!(matrix[0][2], 10.0);
+        
+        let wrong = Vector::new("w", vec![1.0, 2.0, 3.0]).unwrap();
+        assert!(matches!(
+            collection.distance_matrix(&[&wrong], DistanceMetric::Euclidean),
+            Err(ZyphyrError::InvalidDimension { expected: 2, got: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_reservoir_sample() {
+        let collection = random_collection(200, 4, 48);
+        
+        let sample = collection.sample(25, 7);
+        assert_eq!(sample.len(), 25);
+        let mut ids: Vec<&str> = sample.iter().map(|v| v.id()).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), 25, "sample contains duplicates");
+        
+        let again: Vec<&str> = collection.sample(25, 7).iter().map(|v| v.id()).collect();
+        let first: Vec<&str> = sample.iter().map(|v| v.id()).collect();
+        assert_eq!(first, again);
+        let other: Vec<&str> = collection.sample(25, 8).iter().map(|v| v.id()).collect();
+        assert_ne!(first, other);
+        
+        assert_eq!(collection.sample(200, 1).len(), 200);
+        assert_eq!(collection.sample(500, 1).len(), 200);
+        assert!(collection.sample(0, 1).is_empty());
+    }
+
+    #[test]
+    fn test_search_tie_breaking() {
+        let mut collection = VectorCollection::new();
+        // All equidistant from the origin, inserted out of id order
+        for (id, data) in [("d", [1.0, 0.0]), ("b", [0.0, 1.0]), ("e", [-1.0, 0.0]), ("a", [0.0, -1.0]), ("c", [0.6, 0.8])] {
+            collection.insert(Vector::new(id, data.to_vec()).unwrap()).unwrap();
+        }
+        let query = Vector::new("q", vec![0.0, 0.0]).unwrap();
+        
+        let ids = |results: Vec<(String, f32)>| results.into_iter().map(|(id, _)| id).collect::<Vec<_>>();
+        let by_id = ids(collection.search(&query, 5, DistanceMetric::Euclidean).unwrap());
+        assert_eq!(by_id, vec!["a", "b", "c", "d", "e"]);
+        for _ in 0..5 {
+            assert_eq!(ids(collection.search(&query, 5, DistanceMetric::Euclidean).unwrap()), by_id);
+        }
+        
+        let defaults = SearchOptions::default();
+        assert_eq!(defaults.tie_break, TieBreak::ById);
+        assert_eq!(ids(collection.search_with_options(&query, 5, DistanceMetric::Euclidean, &defaults).unwrap()), by_id);
+        
+        let insertion = SearchOptions { tie_break: TieBreak::ByInsertionOrder, ..SearchOptions::default() };
+        let by_insertion = ids(collection.search_with_options(&query, 3, DistanceMetric::Euclidean, &insertion).unwrap());
+        assert_eq!(by_insertion, vec!["d", "b", "e"]);
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut collection = random_collection(50, 4, 52);
+        collection.soft_remove("v3");
+        
+        collection.clear();
+        assert_eq!(collection.len(), 0);
+        assert_eq!(collection.raw_len(), 0);
+        assert!(collection.is_empty());
+        assert!(collection.get("v0").is_none());
+        assert_eq!(collection.dimensions(), None);
+        
+        // A dimension that would have been rejected before is accepted now
+        collection.insert(Vector::new("v0", vec![1.0, 2.0]).unwrap()).unwrap();
+        assert_eq!(collection.dimensions(), Some(2));
+        assert_eq!(collection.len(), 1);
+        
+        // A builder-pinned dimension survives clearing
+        let mut pinned = VectorCollection::builder().dimension(3).build();
+        pinned.insert(Vector::new("a", vec![1.0, 2.0, 3.0]).unwrap()).unwrap();
+        pinned.clear();
+        assert_eq!(pinned.dimensions(), Some(3));
+        assert!(pinned.insert(Vector::new("b", vec![1.0, 2.0]).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_capacity_management() {
+        let mut collection = VectorCollection::new();
+        collection.reserve(100);
+        assert!(collection.capacity() >= 100);
+        
+        let mut filled = random_collection(1000, 8, 53);
+        assert!(filled.capacity() >= 1000);
+        for i in 0..900 {
+            filled.remove(&format!("v{}", i));
+        }
+        assert_eq!(filled.len(), 100);
+        
+        let capacity_before = filled.capacity();
+        let memory_before = filled.memory_usage();
+        filled.shrink_to_fit();
+        assert!(filled.capacity() < capacity_before);
+        assert!(filled.capacity() >= 100);
+        assert!(filled.memory_usage() < memory_before);
+        
+        // Contents are untouched
+        assert!(filled.get("v950").is_some());
+    }
+
+    #[test]
+    fn test_collect_from_iterator() {
+        let collection: VectorCollection = (0..10)
+            .map(|i| Vector::new(format!("v{}", i), vec![i as f32, 1.0]).unwrap())
+            .collect();
+        assert_eq!(collection.len(), 10);
+        assert_eq!(collection.dimensions(), Some(2));
+        assert_eq!(collection.get("v7").unwrap().data(), &[7.0, 1.0]);
+        
+        let duplicate = vec![
+            Vector::new("a", vec![1.0]).unwrap(),
+            Vector::new("a", vec![2.0]).unwrap(),
+        ];
+        assert!(matches!(VectorCollection::try_from_iter(duplicate), Err(ZyphyrError::Other(_))));
+        
+        let mismatched = vec![
+            Vector::new("a", vec![1.0]).unwrap(),
+            Vector::new("b", vec![1.0, 2.0]).unwrap(),
+        ];
+        assert!(matches!(VectorCollection::try_from_iter(mismatched), Err(ZyphyrError::InvalidDimension { .. })));
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate ID: a")]
+    fn test_collect_panics_on_duplicate_id() {
+        let _: VectorCollection = vec![
+            Vector::new("a", vec![1.0]).unwrap(),
+            Vector::new("a", vec![2.0]).unwrap(),
+        ]
+        .into_iter()
+        .collect();
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut collection = random_collection(3, 4, 57);
+        collection.extend((3..5).map(|i| Vector::new(format!("v{}", i), vec![i as f32; 4]).unwrap()));
+        assert_eq!(collection.len(), 5);
+        assert_eq!(collection.get("v4").unwrap().data(), &[4.0; 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid vector dimension")]
+    fn test_extend_panics_on_dimension_mismatch() {
+        let mut collection = random_collection(3, 4, 57);
+        collection.extend(vec![Vector::new("wide", vec![1.0; 5]).unwrap()]);
+    }
+
+    #[test]
+    fn test_collection_stats() {
+        let empty = VectorCollection::new().stats();
+        assert_eq!(empty.count, 0);
+        assert!(empty.mean.is_none() && empty.min.is_none() && empty.max.is_none());
+        assert!(empty.average_norm.is_none());
+        
+        let mut collection = VectorCollection::new();
+        collection.insert(Vector::new("a", vec![3.0, 4.0]).unwrap()).unwrap();
+        collection.insert(Vector::new("b", vec![0.0, -2.0]).unwrap()).unwrap();
+        collection.insert(Vector::new("c", vec![6.0, 1.0]).unwrap()).unwrap();
+        
+        let stats = collection.stats();
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.mean.unwrap(), vec![3.0, 1.0]);
+        assert_eq!(stats.min.unwrap(), vec![0.0, -2.0]);
+        assert_eq!(stats.max.unwrap(), vec![6.0, 4.0]);
+        let expected_norm = (5.0 + 2.0 + 37.0f32.sqrt()) / 3.0;
+        assert!((stats.average_norm.unwrap() - expected_norm).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_find_duplicates() {
+        let mut collection = VectorCollection::new();
+        collection.insert(Vector::new("original", vec![1.0, 2.0, 3.0]).unwrap()).unwrap();
+        collection.insert(Vector::new("far", vec![-50.0, 40.0, 10.0]).unwrap()).unwrap();
+        collection.insert(Vector::new("copy", vec![1.0, 2.0001, 3.0]).unwrap()).unwrap();
+        
+        let pairs = collection.find_duplicates(0.01, DistanceMetric::Euclidean).unwrap();
+        assert_eq!(pairs, vec![("original".to_string(), "copy".to_string())]);
+        
+        assert!(collection.find_duplicates(0.0, DistanceMetric::Euclidean).unwrap().is_empty());
+        assert_eq!(collection.find_duplicates(1000.0, DistanceMetric::Euclidean).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut left = random_collection(3, 2, 61);
+        let mut right = VectorCollection::new();
+        right.insert(Vector::new("r0", vec![1.0, 1.0]).unwrap()).unwrap();
+        right.insert(Vector::new("r1", vec![2.0, 2.0]).unwrap()).unwrap();
+        right.soft_remove("r1");
+        
+        left.merge(right).unwrap();
+        assert_eq!(left.len(), 4);
+        assert_eq!(left.get("r0").unwrap().data(), &[1.0, 1.0]);
+        assert!(!left.contains("r1"));
+        
+        let mut wide = VectorCollection::new();
+        wide.insert(Vector::new("w", vec![1.0, 2.0, 3.0]).unwrap()).unwrap();
+        assert!(matches!(left.merge(wide), Err(ZyphyrError::InvalidDimension { expected: 2, got: 3 })));
+    }
+
+    #[test]
+    fn test_merge_collisions() {
+        let mut left = random_collection(3, 2, 61);
+        let colliding = || {
+            let mut other = VectorCollection::new();
+            other.insert(Vector::new("new", vec![5.0, 5.0]).unwrap()).unwrap();
+            other.insert(Vector::new("v1", vec![9.0, 9.0]).unwrap()).unwrap();
+            other
+        };
+        
+        // A failed merge leaves the target untouched
+        assert!(matches!(left.merge(colliding()), Err(ZyphyrError::Other(_))));
+        assert_eq!(left.len(), 3);
+        assert!(!left.contains("new"));
+        
+        left.merge_with_rename(colliding(), "other_").unwrap();
+        assert_eq!(left.len(), 5);
+        assert_eq!(left.get("new").unwrap().data(), &[5.0, 5.0]);
+        assert_eq!(left.get("other_v1").unwrap().data(), &[9.0, 9.0]);
+        assert_ne!(left.get("v1").unwrap().data(), &[9.0, 9.0]);
+        
+        // Prefixing repeats until the id is unique
+        left.merge_with_rename(colliding(), "other_").unwrap();
+        assert!(left.contains("other_new"));
+        assert!(left.contains("other_other_v1"));
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut collection = VectorCollection::new();
+        for (i, id) in ["keep_a", "drop_a", "keep_b", "drop_b", "keep_c"].iter().enumerate() {
+            collection.insert(Vector::new(*id, vec![i as f32, 1.0]).unwrap()).unwrap();
+        }
+        collection.soft_remove("keep_c");
+        
+        collection.retain(|v| v.id().starts_with("keep"));
+        assert_eq!(collection.len(), 2);
+        assert_eq!(collection.raw_len(), 2);
+        assert!(!collection.contains("drop_a") && !collection.contains("drop_b"));
+        assert!(!collection.contains("keep_c"));
+        assert_eq!(collection.get("keep_b").unwrap().data(), &[2.0, 1.0]);
+        assert_eq!(collection.search(collection.get("keep_b").unwrap(), 1, DistanceMetric::Euclidean).unwrap()[0].0, "keep_b");
+        
+        // Emptying the collection frees it to accept a new dimension
+        collection.retain(|_| false);
+        assert!(collection.is_empty());
+        assert_eq!(collection.dimensions(), None);
+        collection.insert(Vector::new("wide", vec![1.0, 2.0, 3.0]).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_search_farthest() {
+        let mut collection = VectorCollection::new();
+        collection.insert(Vector::new("near", vec![1.0, 0.0]).unwrap()).unwrap();
+        collection.insert(Vector::new("far", vec![10.0, 10.0]).unwrap()).unwrap();
+        collection.insert(Vector::new("mid", vec![3.0, 4.0]).unwrap()).unwrap();
+        let query = Vector::new("q", vec![0.0, 0.0]).unwrap();
+        
+        let results = collection.search_farthest(&query, 2, DistanceMetric::Euclidean).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, "far");
+        assert!((results[0].1 - 200.0f32.sqrt()).abs() < 1e-5);
+        assert_eq!(results[1], ("mid".to_string(), 5.0));
+        
+        let all = collection.search_farthest(&query, 10, DistanceMetric::Euclidean).unwrap();
+        let ids: Vec<&str> = all.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["far", "mid", "near"]);
+    }
+
+    #[test]
+    fn test_search_mips() {
+        let mut collection = VectorCollection::new();
+        collection.insert(Vector::new("small", vec![0.1, 0.1]).unwrap()).unwrap();
+        collection.insert(Vector::new("large", vec![5.0, 4.0]).unwrap()).unwrap();
+        collection.insert(Vector::new("negative", vec![-3.0, -3.0]).unwrap()).unwrap();
+        let query = Vector::new("q", vec![1.0, 2.0]).unwrap();
+        
+        let results = collection.search_mips(&query, 3).unwrap();
+        let ids: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["large", "small", "negative"]);
+        assert!((results[0].1 - 13.0).abs() < 1e-5);
+        
+        // The plain search treats the dot product as a distance and inverts the order
+        let ascending = collection.search(&query, 1, DistanceMetric::DotProduct).unwrap();
+        assert_eq!(ascending[0].0, "negative");
+    }
+
+    #[test]
+    fn test_memory_budget() {
+        let per_vector = Vector::new("v0", vec![0.0; 64]).unwrap().memory_usage();
+        let mut collection = VectorCollection::builder()
+            .max_memory_bytes(per_vector * 3 + per_vector / 2)
+            .build();
+        for i in 0..3 {
+            collection.insert(Vector::new(format!("v{}", i), vec![i as f32; 64]).unwrap()).unwrap();
+        }
+        
+        let result = collection.insert(Vector::new("v3", vec![3.0; 64]).unwrap());
+        match result {
+            Err(ZyphyrError::MemoryLimitExceeded { used, limit }) => {
+                assert_eq!(used, per_vector * 3);
+                assert_eq!(limit, per_vector * 3 + per_vector / 2);
+            }
+            other => panic!("expected MemoryLimitExceeded, got {:?}", other),
+        }
+        assert_eq!(collection.len(), 3);
+        assert!(!collection.contains("v3"));
+        
+        // Freeing space lets inserts succeed again
+        collection.remove("v0");
+        collection.insert(Vector::new("v3", vec![3.0; 64]).unwrap()).unwrap();
+        
+        // A rejected first insert doesn't fix the dimension
+        let mut tiny = VectorCollection::builder().max_memory_bytes(1).build();
+        assert!(tiny.insert(Vector::new("a", vec![1.0; 8]).unwrap()).is_err());
+        assert_eq!(tiny.dimensions(), None);
+    }
+
+    #[test]
+    fn test_incremental_memory_usage() {
+        let mut collection = VectorCollection::new();
+        let check = |collection: &VectorCollection| {
+            assert_eq!(collection.memory_usage(), collection.recompute_memory_usage());
+        };
+        check(&collection);
+        
+        let batch: Vec<Vector> = (0..20)
+            .map(|i| Vector::new(format!("v{}", i), vec![i as f32; 16]).unwrap())
+            .collect();
+        collection.batch_insert(batch).unwrap();
+        check(&collection);
+        
+        // Swap-remove from the middle, the stable variant, and the tail
+        collection.remove("v3");
+        collection.remove_stable("v7");
+        collection.remove("v19");
+        check(&collection);
+        
+        let tagged = Vector::new("v4", vec![1.0; 16]).unwrap()
+            .with_metadata([("key".to_string(), "a much longer value".to_string())].into());
+        collection.upsert(tagged).unwrap();
+        check(&collection);
+        
+        collection.soft_remove("v5");
+        check(&collection);
+        collection.compact();
+        check(&collection);
+        collection.retain(|v| v.id() != "v6");
+        check(&collection);
+        
+        collection.clear();
+        check(&collection);
+    }
+
+    #[test]
+    fn test_search_with_budget() {
+        let collection = random_collection(200, 8, 74);
+        let query = Vector::new("q", vec![0.1; 8]).unwrap();
+        
+        let full = collection.search(&query, 10, DistanceMetric::Euclidean).unwrap();
+        let budgeted = collection.search_with_budget(&query, 10, DistanceMetric::Euclidean, collection.len()).unwrap();
+        assert_eq!(budgeted, full);
+        
+        // A smaller budget only ever sees the first vectors in storage order
+        let scanned: Vec<&str> = collection.iter().take(50).map(|v| v.id()).collect();
+        let partial = collection.search_with_budget(&query, 10, DistanceMetric::Euclidean, 50).unwrap();
+        assert_eq!(partial.len(), 10);
+        assert!(partial.iter().all(|(id, _)| scanned.contains(&id.as_str())));
+        assert_eq!(
+            partial,
+            collection.search_filtered(&query, 10, DistanceMetric::Euclidean, |v| scanned.contains(&v.id())).unwrap()
+        );
+        
+        assert_eq!(collection.search_with_budget(&query, 10, DistanceMetric::Euclidean, 3).unwrap().len(), 3);
+        assert!(collection.search_with_budget(&query, 10, DistanceMetric::Euclidean, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_to_contiguous() {
+        let mut collection = random_collection(20, 13, 79);
+        collection.soft_remove("v4");
+        
+        let (buffer, stride) = collection.to_contiguous();
+        let padded_dim = collection.iter().next().unwrap().padded_dim();
+        assert_eq!(stride, padded_dim);
+        assert_eq!(buffer.len(), collection.len() * padded_dim);
+        for (i, v) in collection.iter().enumerate() {
+            assert_eq!(&buffer[i * stride..(i + 1) * stride], v.raw_data());
+        }
+        
+        assert_eq!(VectorCollection::new().to_contiguous(), (Vec::new(), 0));
+    }
+
+    #[test]
+    fn test_merge_search_results() {
+        let shard = |hits: &[(&str, f32)]| -> Vec<(String, f32)> {
+            hits.iter().map(|(id, d)| (id.to_string(), *d)).collect()
+        };
+        let results = vec![
+            shard(&[("a", 0.1), ("d", 0.5), ("f", 0.9)]),
+            shard(&[("b", 0.2), ("a", 0.3), ("e", 0.6)]),
+            shard(&[("c", 0.25), ("d", 0.4)]),
+        ];
+        
+        let merged = merge_search_results(&results, 5);
+        assert_eq!(merged, shard(&[("a", 0.1), ("b", 0.2), ("c", 0.25), ("d", 0.4), ("e", 0.6)]));
+        
+        // Asking for more than exists returns every distinct id once
+        assert_eq!(merge_search_results(&results, 100).len(), 6);
+        assert!(merge_search_results(&results, 0).is_empty());
+        assert!(merge_search_results(&[Vec::new(), Vec::new()], 3).is_empty());
+        
+        // Merging real per-shard searches matches one search over the union
+        let all = random_collection(60, 4, 80);
+        let mut shards = [VectorCollection::new(), VectorCollection::new(), VectorCollection::new()];
+        for (i, v) in all.iter().enumerate() {
+            shards[i % 3].insert(v.clone()).unwrap();
+        }
+        let query = Vector::new("q", vec![0.2, -0.1, 0.4, 0.0]).unwrap();
+        let per_shard: Vec<_> = shards
+            .iter()
+            .map(|s| s.search(&query, 10, DistanceMetric::Euclidean).unwrap())
+            .collect();
+        assert_eq!(
+            merge_search_results(&per_shard, 10),
+            all.search(&query, 10, DistanceMetric::Euclidean).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_reindex() {
+        let mut collection = random_collection(20, 4, 82);
+        for i in [3, 7, 0, 15] {
+            collection.remove(&format!("v{}", i));
+        }
+        collection.soft_remove("v9");
+        for i in 20..25 {
+            collection.insert(Vector::new(format!("v{}", i), vec![i as f32; 4]).unwrap()).unwrap();
+        }
+        collection.remove("v21");
+        
+        collection.reindex();
+        assert_eq!(collection.len(), 19);
+        assert!(!collection.contains("v9") && !collection.contains("v21"));
+        for id in collection.ids().map(str::to_string).collect::<Vec<_>>() {
+            assert_eq!(collection.get(&id).unwrap().id(), id);
+        }
+        assert_eq!(collection.memory_usage(), collection.recompute_memory_usage());
+        assert_eq!(collection.get("v24").unwrap().data(), &[24.0; 4]);
+    }
+
+    #[test]
+    fn test_search_query_dimension_mismatch() {
+        let mut collection = VectorCollection::new();
+        collection.insert(Vector::new("a", vec![1.0, 0.0]).unwrap()).unwrap();
+        let query = Vector::new("q", vec![1.0, 0.0, 0.0]).unwrap();
+        
+        for k in [0, 1] {
+            match collection.search(&query, k, DistanceMetric::Euclidean) {
+                Err(ZyphyrError::InvalidDimension { expected, got }) => {
+                    assert_eq!((expected, got), (2, 3));
+                }
+                other => panic!("expected InvalidDimension, got {:?}", other),
+            }
+        }
+        
+        // An empty collection has no dimension to enforce yet
+        assert!(VectorCollection::new().search(&query, 1, DistanceMetric::Euclidean).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_insert_stream() {
+        let mut collection = VectorCollection::new();
+        let items = (0..5).map(|i| {
+            if i == 2 {
+                Err(ZyphyrError::InvalidData("bad record".to_string()))
+            } else {
+                Vector::new(format!("v{}", i), vec![i as f32, 1.0])
+            }
+        });
+        
+        assert!(matches!(collection.insert_stream(items), Err(ZyphyrError::InvalidData(_))));
+        assert_eq!(collection.len(), 2);
+        assert!(collection.contains("v0") && collection.contains("v1"));
+        assert!(!collection.contains("v3"));
+        
+        let rest = (3..6).map(|i| Vector::new(format!("v{}", i), vec![i as f32, 1.0]));
+        assert_eq!(collection.insert_stream(rest).unwrap(), 3);
+        assert_eq!(collection.len(), 5);
+        
+        // Insert failures stop the stream too; the change in len() is the count kept
+        let before = collection.len();
+        let dupes = ["v6", "v0", "v7"].into_iter().map(|id| Vector::new(id, vec![0.0, 0.0]));
+        assert!(collection.insert_stream(dupes).is_err());
+        assert_eq!(collection.len() - before, 1);
+        assert!(collection.contains("v6") && !collection.contains("v7"));
+    }
+
+    #[test]
+    fn test_search_prefetch_toggle() {
+        let collection = random_collection(200, 24, 88);
+        let query = Vector::new("q", vec![0.1; 24]).unwrap();
+        assert!(SearchOptions::default().prefetch);
+        
+        let without = SearchOptions { prefetch: false, ..SearchOptions::default() };
+        for metric in [DistanceMetric::Euclidean, DistanceMetric::Cosine] {
+            let expected = collection.search(&query, 15, metric.clone()).unwrap();
+            assert_eq!(collection.search_with_options(&query, 15, metric.clone(), &without).unwrap(), expected);
+            assert_eq!(collection.search_with_options(&query, 15, metric, &SearchOptions::default()).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_search_into_reuses_scratch() {
+        let collection = random_collection(300, 12, 91);
+        let mut rng = StdRng::seed_from_u64(92);
+        let mut scratch = SearchScratch::new();
+        
+        // Shrinking and growing k between calls must not leak stale hits
+        for (round, k) in [10, 3, 0, 25, 300, 1000, 7].into_iter().enumerate() {
+            let metric = if round % 2 == 0 { DistanceMetric::Euclidean } else { DistanceMetric::Cosine };
+            let data: Vec<f32> = (0..12).map(|_| rng.random_range(-1.0..1.0)).collect();
+            let query = Vector::new("q", data).unwrap();
+            
+            let expected = collection.search(&query, k, metric.clone()).unwrap();
+            let hits = collection.search_into(&query, k, metric, &mut scratch).unwrap();
+            assert_eq!(hits, expected.as_slice(), "round {}, k {}", round, k);
+        }
+        
+        let wrong = Vector::new("q", vec![0.0; 3]).unwrap();
+        assert!(collection.search_into(&wrong, 5, DistanceMetric::Euclidean, &mut scratch).is_err());
+        assert!(VectorCollection::new().search_into(&wrong, 5, DistanceMetric::Euclidean, &mut scratch).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_rename() {
+        let mut collection = random_collection(5, 3, 93);
+        let data = collection.get("v2").unwrap().data().to_vec();
+        let position = collection.ids().position(|id| id == "v2").unwrap();
+        
+        collection.rename("v2", "renamed_with_a_longer_id").unwrap();
+        assert!(!collection.contains("v2"));
+        let renamed = collection.get("renamed_with_a_longer_id").unwrap();
+        assert_eq!(renamed.id(), "renamed_with_a_longer_id");
+        assert_eq!(renamed.data(), data.as_slice());
+        assert_eq!(collection.ids().position(|id| id == "renamed_with_a_longer_id"), Some(position));
+        assert_eq!(collection.len(), 5);
+        assert_eq!(collection.memory_usage(), collection.recompute_memory_usage());
+        
+        let query = Vector::new("q", data).unwrap();
+        assert_eq!(collection.search(&query, 1, DistanceMetric::Euclidean).unwrap()[0].0, "renamed_with_a_longer_id");
+        
+        // Renaming to itself is a no-op
+        collection.rename("v0", "v0").unwrap();
+        assert!(collection.contains("v0"));
+    }
+
+    #[test]
+    fn test_rename_errors() {
+        let mut collection = random_collection(3, 2, 94);
+        assert!(matches!(collection.rename("missing", "x"), Err(ZyphyrError::IdNotFound(id)) if id == "missing"));
+        assert!(matches!(collection.rename("v0", "v1"), Err(ZyphyrError::Other(_))));
+        
+        // Failed renames leave everything in place
+        assert!(collection.contains("v0") && collection.contains("v1"));
+        assert!(!collection.contains("x"));
+        assert_eq!(collection.get("v0").unwrap().id(), "v0");
+        
+        collection.soft_remove("v2");
+        assert!(matches!(collection.rename("v2", "y"), Err(ZyphyrError::IdNotFound(_))));
+    }
+
+    #[test]
+    fn test_batch_search_metrics() {
+        let mut collection = VectorCollection::new();
+        // "long" points the same way as the query but is far away; "near" is
+        // close by but at an angle
+        collection.insert(Vector::new("long", vec![10.0, 10.0]).unwrap()).unwrap();
+        collection.insert(Vector::new("near", vec![1.5, 0.5]).unwrap()).unwrap();
+        collection.insert(Vector::new("opposite", vec![-1.0, -1.0]).unwrap()).unwrap();
+        let query = Vector::new("q", vec![1.0, 1.0]).unwrap();
+        
+        let results = collection
+            .batch_search_metrics(&[(&query, DistanceMetric::Euclidean), (&query, DistanceMetric::Cosine)], 3)
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0], collection.search(&query, 3, DistanceMetric::Euclidean).unwrap());
+        assert_eq!(results[1], collection.search(&query, 3, DistanceMetric::Cosine).unwrap());
+        assert_eq!(results[0][0].0, "near");
+        assert_eq!(results[1][0].0, "long");
+        assert_ne!(results[0], results[1]);
+        
+        // Mixed metrics over a larger collection match individual searches
+        let random = random_collection(150, 6, 95);
+        let q1 = Vector::new("q1", vec![0.4; 6]).unwrap();
+        let q2 = Vector::new("q2", vec![-0.2, 0.1, 0.0, 0.9, -0.5, 0.3]).unwrap();
+        let queries = [(&q1, DistanceMetric::DotProduct), (&q2, DistanceMetric::Cosine), (&q1, DistanceMetric::Tanimoto)];
+        let batch = random.batch_search_metrics(&queries, 8).unwrap();
+        for ((query, metric), got) in queries.iter().zip(&batch) {
+            assert_eq!(got, &random.search(query, 8, metric.clone()).unwrap());
+        }
+        
+        assert!(random.batch_search_metrics(&[], 5).unwrap().is_empty());
+        let wrong = Vector::new("w", vec![1.0; 2]).unwrap();
+        assert!(random.batch_search_metrics(&[(&q1, DistanceMetric::Euclidean), (&wrong, DistanceMetric::Euclidean)], 5).is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let build = || {
+            let mut collection = random_collection(20, 4, 96);
+            collection.remove("v3");
+            collection.soft_remove("v7");
+            collection
+        };
+        random_collection(20, 4, 96).validate().unwrap();
+        build().validate().unwrap();
+        VectorCollection::new().validate().unwrap();
+        
+        let is_corrupt = |c: &VectorCollection| matches!(c.validate(), Err(ZyphyrError::Corrupt(_)));
+        
+        // Re-keying a vector behind the id map's back leaves a stale entry and a duplicate
+        let mut stale = build();
+        stale.get_mut("v1").unwrap().set_id("v0".to_string());
+        assert!(is_corrupt(&stale));
+        
+        // Swapping in a vector of another dimension
+        let mut wrong_dim = build();
+        *wrong_dim.get_mut("v5").unwrap() = Vector::new("v5", vec![1.0; 6]).unwrap();
+        match wrong_dim.validate() {
+            Err(ZyphyrError::Corrupt(message)) => assert!(message.contains("dimension"), "{}", message),
+            other => panic!("expected Corrupt, got {:?}", other),
+        }
+        
+        // Reindexing rebuilds the map from the stored ids, which repairs a rename
+        let mut renamed = build();
+        renamed.get_mut("v2").unwrap().set_id("fresh".to_string());
+        assert!(is_corrupt(&renamed));
+        renamed.reindex();
+        renamed.validate().unwrap();
+        assert!(renamed.contains("fresh") && !renamed.contains("v2"));
+    }
+
+    #[test]
+    fn test_get_many() {
+        let mut collection = random_collection(10, 3, 97);
+        collection.soft_remove("v4");
+        
+        let found = collection.get_many(&["v2", "missing", "v9", "v4", "v2"]);
+        let ids: Vec<Option<&str>> = found.iter().map(|v| v.map(|v| v.id())).collect();
+        assert_eq!(ids, vec![Some("v2"), None, Some("v9"), None, Some("v2")]);
+        assert!(collection.get_many(&[]).is_empty());
+        
+        let all = collection.try_get_many(&["v0", "v1", "v2"]).unwrap();
+        assert_eq!(all.iter().map(|v| v.id()).collect::<Vec<_>>(), vec!["v0", "v1", "v2"]);
+        assert!(matches!(
+            collection.try_get_many(&["v0", "gone", "v4"]),
+            Err(ZyphyrError::IdNotFound(id)) if id == "gone"
+        ));
+    }
+
+    #[test]
+    fn test_search_slice_matches_vector_query() {
+        let mut rng = StdRng::seed_from_u64(97);
+        let metrics = [
+            DistanceMetric::Euclidean,
+            DistanceMetric::Cosine,
+            DistanceMetric::DotProduct,
+            DistanceMetric::Angular,
+            DistanceMetric::Correlation,
+            DistanceMetric::Tanimoto,
+        ];
+        
+        // Dimensions on and off the SIMD chunk boundaries
+        for dim in [3, 13, 16, 37] {
+            let collection = random_collection(120, dim, dim as u64);
+            let data: Vec<f32> = (0..dim).map(|_| rng.random_range(-1.0..1.0)).collect();
+            let query = Vector::new("q", data.clone()).unwrap();
+            for metric in &metrics {
+                assert_eq!(
+                    collection.search_slice(&data, 10, metric.clone()).unwrap(),
+                    collection.search(&query, 10, metric.clone()).unwrap(),
+                    "dim {}, metric {:?}", dim, metric
+                );
+            }
+        }
+        
+        let mut normalized = VectorCollection::builder().normalize_on_insert(true).build();
+        for v in random_collection(50, 5, 98).iter() {
+            normalized.insert(v.clone()).unwrap();
+        }
+        let data = [0.3, -0.2, 0.9, 0.1, 0.0];
+        let query = Vector::new("q", data.to_vec()).unwrap();
+        assert_eq!(
+            normalized.search_slice(&data, 5, DistanceMetric::Cosine).unwrap(),
+            normalized.search(&query, 5, DistanceMetric::Cosine).unwrap()
+        );
+        
+        assert!(matches!(
+            normalized.search_slice(&[1.0, 2.0], 5, DistanceMetric::Euclidean),
+            Err(ZyphyrError::InvalidDimension { expected: 5, got: 2 })
+        ));
+        assert!(matches!(
+            normalized.search_slice(&[f32::NAN, 0.0, 0.0, 0.0, 0.0], 5, DistanceMetric::Euclidean),
+            Err(ZyphyrError::InvalidData(_))
+        ));
+        assert!(VectorCollection::new().search_slice(&[1.0], 3, DistanceMetric::Cosine).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_search_thresholded() {
+        let collection = random_collection(100, 8, 99);
+        let target = collection.get("v42").unwrap().clone();
+        
+        // Only the exact match is within a tight threshold
+        let hits = collection.search_thresholded(&target, 10, DistanceMetric::Euclidean, 1e-4).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].0, "v42");
+        
+        // A loose threshold is the same as a plain search
+        let plain = collection.search(&target, 10, DistanceMetric::Euclidean).unwrap();
+        assert_eq!(collection.search_thresholded(&target, 10, DistanceMetric::Euclidean, f32::INFINITY).unwrap(), plain);
+        
+        // A threshold in between keeps exactly the prefix of the plain results under it
+        let cutoff = plain[4].1;
+        let partial = collection.search_thresholded(&target, 10, DistanceMetric::Euclidean, cutoff).unwrap();
+        assert_eq!(partial, plain[..5]);
+        
+        let far = Vector::new("far", vec![100.0; 8]).unwrap();
+        assert!(collection.search_thresholded(&far, 10, DistanceMetric::Euclidean, 1.0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sharded_search_matches_unsharded() {
+        let all = random_collection(400, 10, 100);
+        let mut sharded = ShardedCollection::new(4).unwrap();
+        for v in all.iter() {
+            sharded.insert(v.clone()).unwrap();
+        }
+        assert_eq!(sharded.len(), 400);
+        assert_eq!(sharded.num_shards(), 4);
+        assert_eq!(sharded.dimensions(), Some(10));
+        // Hash routing spreads ids over every shard
+        assert!(sharded.shards().iter().all(|shard| !shard.is_empty()));
+        
+        let mut rng = StdRng::seed_from_u64(101);
+        for metric in [DistanceMetric::Euclidean, DistanceMetric::Cosine] {
+            for k in [1, 10, 50] {
+                let data: Vec<f32> = (0..10).map(|_| rng.random_range(-1.0..1.0)).collect();
+                let query = Vector::new("q", data).unwrap();
+                assert_eq!(
+                    sharded.search(&query, k, metric.clone()).unwrap(),
+                    all.search(&query, k, metric.clone()).unwrap()
+                );
+            }
+        }
+        
+        assert_eq!(sharded.get("v17").unwrap().data(), all.get("v17").unwrap().data());
+        assert!(sharded.insert(all.get("v17").unwrap().clone()).is_err());
+        assert!(sharded.insert(Vector::new("wide", vec![0.0; 11]).unwrap()).is_err());
+        assert!(sharded.remove("v17").is_some());
+        assert!(!sharded.contains("v17"));
+        assert_eq!(sharded.len(), 399);
+        
+        assert!(ShardedCollection::new(0).is_err());
+        let empty = ShardedCollection::new(3).unwrap();
+        assert!(empty.is_empty());
+        assert!(empty.search(&Vector::new("q", vec![1.0]).unwrap(), 5, DistanceMetric::Euclidean).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_center() {
+        let mut collection = VectorCollection::new();
+        let mut rng = StdRng::seed_from_u64(101);
+        for i in 0..200 {
+            // Offset every dimension so the mean is clearly nonzero
+            let data: Vec<f32> = (0..6).map(|d| d as f32 + rng.random_range(-1.0..1.0)).collect();
+            collection.insert(Vector::new(format!("v{}", i), data).unwrap()).unwrap();
+        }
+        let metadata: HashMap<String, String> = [("k".to_string(), "v".to_string())].into();
+        collection.upsert(collection.get("v0").unwrap().clone().with_metadata(metadata.clone())).unwrap();
+        let before = collection.get("v5").unwrap().data().to_vec();
+        
+        let mean = collection.center().unwrap();
+        assert_eq!(mean.id(), "mean");
+        assert!((mean.data()[3] - 3.0).abs() < 0.2);
+        for x in collection.stats().mean.unwrap() {
+            assert!(x.abs() < 1e-5, "post-centering mean {}", x);
+        }
+        for (d, x) in collection.get("v5").unwrap().data().iter().enumerate() {
+            assert!((x - (before[d] - mean.data()[d])).abs() < 1e-6);
+        }
+        assert_eq!(collection.get("v0").unwrap().metadata(), Some(&metadata));
+        assert_eq!(collection.memory_usage(), collection.recompute_memory_usage());
+        
+        // Centered queries find the same neighbors as before centering
+        let query = Vector::new("q", before.clone()).unwrap().sub(&mean, "q").unwrap();
+        assert_eq!(collection.search(&query, 1, DistanceMetric::Euclidean).unwrap()[0].0, "v5");
+        
+        assert!(VectorCollection::new().center().is_err());
+    }
+
+    #[test]
+    fn test_normalize_all() {
+        let mut collection = random_collection(300, 9, 102);
+        collection.insert(Vector::new("zero", vec![0.0; 9]).unwrap()).unwrap();
+        collection.insert(Vector::new("big", vec![40.0; 9]).unwrap()).unwrap();
+        
+        collection.normalize_all();
+        for v in collection.iter() {
+            let norm = v.data().iter().map(|x| x * x).sum::<f32>().sqrt();
+            if v.id() == "zero" {
+                assert_eq!(norm, 0.0);
+            } else {
+                assert!((norm - 1.0).abs() < 1e-5, "{} has norm {}", v.id(), norm);
+                assert!(v.is_normalized());
+            }
+        }
+        
+        // Cosine now ranks like the dot product, best match first
+        let query = Vector::new("q", vec![0.5, -0.1, 0.3, 0.0, 0.2, 0.9, -0.4, 0.1, 0.0]).unwrap();
+        let cosine: Vec<String> = collection.search(&query, 20, DistanceMetric::Cosine).unwrap().into_iter().map(|(id, _)| id).collect();
+        let mips: Vec<String> = collection.search_mips(&query, 20).unwrap().into_iter().map(|(id, _)| id).collect();
+        assert_eq!(cosine, mips);
+    }
+
+    #[test]
+    fn test_distance_histogram() {
+        let mut collection = VectorCollection::new();
+        for (i, x) in [0.0, 1.0, 2.0, 3.0, 4.0, 4.5, 8.0].iter().enumerate() {
+            collection.insert(Vector::new(format!("v{}", i), vec![*x, 0.0]).unwrap()).unwrap();
+        }
+        let origin = Vector::new("q", vec![0.0, 0.0]).unwrap();
+        
+        let histogram = collection.distance_histogram(&origin, DistanceMetric::Euclidean, 4).unwrap();
+        assert_eq!(histogram, vec![(2.0, 2), (4.0, 2), (6.0, 2), (8.0, 1)]);
+        assert_eq!(histogram.iter().map(|(_, count)| count).sum::<usize>(), collection.len());
+        
+        let random = random_collection(250, 5, 103);
+        let query = Vector::new("q", vec![0.1, 0.2, -0.3, 0.0, 0.5]).unwrap();
+        for bins in [1, 7, 50] {
+            let histogram = random.distance_histogram(&query, DistanceMetric::Cosine, bins).unwrap();
+            assert_eq!(histogram.len(), bins);
+            assert_eq!(histogram.iter().map(|(_, count)| count).sum::<usize>(), random.len());
+            assert!(histogram.windows(2).all(|w| w[0].0 <= w[1].0));
+        }
+        
+        // Identical distances all fall in the first bin
+        let mut same = VectorCollection::new();
+        same.insert(Vector::new("a", vec![1.0, 0.0]).unwrap()).unwrap();
+        same.insert(Vector::new("b", vec![0.0, 1.0]).unwrap()).unwrap();
+        assert_eq!(same.distance_histogram(&origin, DistanceMetric::Euclidean, 3).unwrap(), vec![(1.0, 2), (1.0, 0), (1.0, 0)]);
+        
+        assert!(collection.distance_histogram(&origin, DistanceMetric::Euclidean, 0).is_err());
+        assert!(VectorCollection::new().distance_histogram(&origin, DistanceMetric::Euclidean, 4).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,242 @@
+#[cfg(test)]
+mod tests {
+    use crate::{Vector, VectorCollection, DistanceMetric, ZyphyrError};
+    use crate::{Distance, Euclidean, Cosine, DotProduct};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_cosine_similarity() {
+        let x = Vector::new("x", vec![1.0, 0.0]).unwrap();
+        let y = Vector::new("y", vec![0.0, 2.0]).unwrap();
+        let neg = Vector::new("neg", vec![-3.0, 0.0]).unwrap();
+        let zero = Vector::new("zero", vec![0.0, 0.0]).unwrap();
+        
+        assert!(DistanceMetric::cosine_similarity(&x, &y).unwrap().abs() < 1e-6);
+        assert!((DistanceMetric::cosine_similarity(&x, &x).unwrap() - 1.0).abs() < 1e-6);
+        assert!((DistanceMetric::cosine_similarity(&x, &neg).unwrap() + 1.0).abs() < 1e-6);
+        assert_eq!(DistanceMetric::cosine_similarity(&x, &zero).unwrap(), 0.0);
+        
+        // Distance stays consistent with similarity, including for zero vectors
+        let distance = DistanceMetric::Cosine.compute(&x, &y).unwrap();
+        assert!((distance - (1.0 - DistanceMetric::cosine_similarity(&x, &y).unwrap())).abs() < 1e-6);
+        assert_eq!(DistanceMetric::Cosine.compute(&zero, &zero).unwrap(), 1.0);
+        
+        let z = Vector::new("z", vec![1.0, 0.0, 0.0]).unwrap();
+        assert!(DistanceMetric::cosine_similarity(&x, &z).is_err());
+    }
+
+    #[test]
+    fn test_weighted_euclidean() {
+        let a = Vector::new("a", vec![1.0, 2.0, 3.0]).unwrap();
+        let b = Vector::new("b", vec![1.0, 7.0, 3.0]).unwrap();
+        
+        // Zeroing the only differing dimension hides the difference entirely
+        let ignore_middle = DistanceMetric::WeightedEuclidean { weights: Arc::new(vec![1.0, 0.0, 1.0]) };
+        assert_eq!(ignore_middle.compute(&a, &b).unwrap(), 0.0);
+        
+        let uniform = DistanceMetric::WeightedEuclidean { weights: Arc::new(vec![1.0; 3]) };
+        let plain = DistanceMetric::Euclidean.compute(&a, &b).unwrap();
+        assert!((uniform.compute(&a, &b).unwrap() - plain).abs() < 1e-6);
+        
+        let doubled = DistanceMetric::WeightedEuclidean { weights: Arc::new(vec![1.0, 4.0, 1.0]) };
+        assert!((doubled.compute(&a, &b).unwrap() - 10.0).abs() < 1e-6);
+        
+        let short = DistanceMetric::WeightedEuclidean { weights: Arc::new(vec![1.0, 1.0]) };
+        assert!(matches!(short.compute(&a, &b), Err(ZyphyrError::InvalidDimension { expected: 3, got: 2 })));
+        assert!(short.compute_slices(a.data(), b.data()).is_err());
+        
+        // Negative or non-finite weights could make the sum negative and the distance NaN
+        assert_eq!(DistanceMetric::weighted_euclidean(vec![1.0, 0.0, 1.0]).unwrap(), ignore_middle);
+        for bad in [-1.0, f32::NAN, f32::INFINITY] {
+            assert!(matches!(DistanceMetric::weighted_euclidean(vec![1.0, bad, 1.0]), Err(ZyphyrError::InvalidData(_))));
+        }
+    }
+
+    #[test]
+    fn test_jensen_shannon() {
+        let p = Vector::new("p", vec![0.2, 0.3, 0.5]).unwrap();
+        // Same distribution before normalization
+        let scaled = Vector::new("scaled", vec![2.0, 3.0, 5.0]).unwrap();
+        assert!(DistanceMetric::JensenShannon.compute(&p, &scaled).unwrap().abs() < 1e-6);
+        
+        let left = Vector::new("left", vec![1.0, 1.0, 0.0, 0.0]).unwrap();
+        let right = Vector::new("right", vec![0.0, 0.0, 3.0, 1.0]).unwrap();
+        let disjoint = DistanceMetric::JensenShannon.compute(&left, &right).unwrap();
+        assert!((disjoint - 1.0).abs() < 1e-6, "disjoint support gave {}", disjoint);
+        
+        // Symmetric and strictly between the extremes for overlapping support
+        let q = Vector::new("q", vec![0.5, 0.3, 0.2]).unwrap();
+        let pq = DistanceMetric::JensenShannon.compute(&p, &q).unwrap();
+        let qp = DistanceMetric::JensenShannon.compute(&q, &p).unwrap();
+        assert!((pq - qp).abs() < 1e-6);
+        assert!(pq > 0.0 && pq < 1.0);
+        
+        let negative = Vector::new("neg", vec![0.5, -0.1, 0.6]).unwrap();
+        assert!(matches!(DistanceMetric::JensenShannon.compute(&p, &negative), Err(ZyphyrError::InvalidData(_))));
+        let zero = Vector::new("zero", vec![0.0, 0.0, 0.0]).unwrap();
+        assert!(matches!(DistanceMetric::JensenShannon.compute(&p, &zero), Err(ZyphyrError::InvalidData(_))));
+    }
+
+    #[test]
+    fn test_correlation_distance() {
+        let a = Vector::new("a", vec![1.0, 3.0, 2.0, 5.0]).unwrap();
+        // Positively scaled and shifted copy of `a`
+        let shifted = Vector::new("shifted", vec![12.0, 16.0, 14.0, 20.0]).unwrap();
+        assert!(DistanceMetric::Correlation.compute(&a, &shifted).unwrap().abs() < 1e-5);
+        
+        // Perfect anti-correlation is the maximum distance of 2
+        let inverted = Vector::new("inverted", vec![-1.0, -3.0, -2.0, -5.0]).unwrap();
+        assert!((DistanceMetric::Correlation.compute(&a, &inverted).unwrap() - 2.0).abs() < 1e-5);
+        
+        let constant = Vector::new("constant", vec![4.0; 4]).unwrap();
+        assert_eq!(DistanceMetric::Correlation.compute(&a, &constant).unwrap(), 1.0);
+        assert_eq!(DistanceMetric::Correlation.compute_slices(&[4.0; 4], &[4.0; 4]).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn test_angular_distance() {
+        use std::f32::consts::{FRAC_PI_2, PI};
+        
+        let x = Vector::new("x", vec![1.0, 0.0]).unwrap();
+        let y = Vector::new("y", vec![0.0, 3.0]).unwrap();
+        let neg = Vector::new("neg", vec![-2.0, 0.0]).unwrap();
+        
+        assert!((DistanceMetric::Angular.compute(&x, &y).unwrap() - FRAC_PI_2).abs() < 1e-6);
+        assert!((DistanceMetric::Angular.compute(&x, &neg).unwrap() - PI).abs() < 1e-6);
+        
+        // Identical vectors must give exactly zero rather than NaN from rounding
+        let v = Vector::new("v", vec![0.1, 0.2, 0.7, 0.3]).unwrap();
+        let same = DistanceMetric::Angular.compute(&v, &v).unwrap();
+        assert!(!same.is_nan());
+        assert!(same.abs() < 1e-3);
+        
+        // Triangle inequality through an intermediate direction
+        let diag = Vector::new("diag", vec![1.0, 1.0]).unwrap();
+        let via = DistanceMetric::Angular.compute(&x, &diag).unwrap() + DistanceMetric::Angular.compute(&diag, &y).unwrap();
+        assert!(DistanceMetric::Angular.compute(&x, &y).unwrap() <= via + 1e-6);
+    }
+
+    #[test]
+    fn test_custom_metric() {
+        let mut collection = VectorCollection::new();
+        collection.insert(Vector::new("b", vec![1.0, 2.0]).unwrap()).unwrap();
+        collection.insert(Vector::new("a", vec![100.0, -5.0]).unwrap()).unwrap();
+        let query = Vector::new("q", vec![1.0, 2.0]).unwrap();
+        
+        let constant = DistanceMetric::Custom(Arc::new(|_, _| 0.5));
+        let results = collection.search(&query, 2, constant.clone()).unwrap();
+        assert_eq!(results, vec![("a".to_string(), 0.5), ("b".to_string(), 0.5)]);
+        
+        // The closure sees only the real dimensions, never the padding
+        let manhattan = DistanceMetric::Custom(Arc::new(|a: &[f32], b: &[f32]| {
+            assert_eq!(a.len(), 2);
+            a.iter().zip(b).map(|(x, y)| (x - y).abs()).sum()
+        }));
+        let results = collection.search(&query, 2, manhattan).unwrap();
+        assert_eq!(results[0], ("b".to_string(), 0.0));
+        assert_eq!(results[1], ("a".to_string(), 106.0));
+        
+        let wide = Vector::new("w", vec![1.0, 2.0, 3.0]).unwrap();
+        assert!(matches!(constant.compute(&query, &wide), Err(ZyphyrError::InvalidDimension { .. })));
+        
+        assert_eq!(constant, constant.clone());
+        assert_ne!(constant, DistanceMetric::Custom(Arc::new(|_, _| 0.5)));
+        assert_eq!(format!("{:?}", constant), "Custom(..)");
+    }
+
+    #[test]
+    fn test_generic_distance_matches_enum() {
+        let a = Vector::new("a", vec![1.0, -2.0, 3.0, 0.5, 4.0]).unwrap();
+        let b = Vector::new("b", vec![0.5, 1.0, -1.0, 2.0, 3.0]).unwrap();
+        let pairs = [
+            (Euclidean::distance(a.raw_data(), b.raw_data()), DistanceMetric::Euclidean),
+            (Cosine::distance(a.raw_data(), b.raw_data()), DistanceMetric::Cosine),
+            (DotProduct::distance(a.raw_data(), b.raw_data()), DistanceMetric::DotProduct),
+        ];
+        for (generic, metric) in pairs {
+            assert_eq!(generic, metric.compute(&a, &b).unwrap(), "{:?}", metric);
+            assert_eq!(generic, metric.compute_slices(a.raw_data(), b.raw_data()).unwrap(), "{:?}", metric);
+        }
+        
+        let mut collection = VectorCollection::new();
+        for i in 0..50 {
+            let data: Vec<f32> = (0..5).map(|d| ((i * 7 + d * 3) % 11) as f32 - 5.0).collect();
+            collection.insert(Vector::new(format!("v{}", i), data).unwrap()).unwrap();
+        }
+        let generic = collection.search_generic::<Euclidean>(&a, 5).unwrap();
+        let dynamic = collection.search(&a, 5, DistanceMetric::Euclidean).unwrap();
+        assert_eq!(generic, dynamic);
+        
+        let wide = Vector::new("w", vec![1.0; 6]).unwrap();
+        assert!(collection.search_generic::<Cosine>(&wide, 5).is_err());
+        
+        // Generic cosine search takes the same unit-vector shortcut as the enum
+        let mut normalized = VectorCollection::builder().normalize_on_insert(true).build();
+        for v in collection.iter() {
+            normalized.insert(v.clone()).unwrap();
+        }
+        assert_eq!(
+            normalized.search_generic::<Cosine>(&a, 5).unwrap(),
+            normalized.search(&a, 5, DistanceMetric::Cosine).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_fused_cosine_matches_scalar() {
+        // Scalar reference over the real dimensions only
+        fn reference(a: &[f32], b: &[f32]) -> f32 {
+            let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+            let a_mag = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+            let b_mag = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+            1.0 - dot / (a_mag * b_mag)
+        }
+        
+        for dim in [1, 7, 8, 13, 100, 1024] {
+            let a_data: Vec<f32> = (0..dim).map(|i| ((i * 37 % 23) as f32 - 11.0) / 7.0).collect();
+            let b_data: Vec<f32> = (0..dim).map(|i| ((i * 17 % 29) as f32 - 13.0) / 5.0 + 0.5).collect();
+            let a = Vector::new("a", a_data.clone()).unwrap();
+            let b = Vector::new("b", b_data.clone()).unwrap();
+            assert!(a.cached_norm().is_none());
+            
+            let expected = reference(&a_data, &b_data);
+            let fused = DistanceMetric::Cosine.compute(&a, &b).unwrap();
+            assert!((fused - expected).abs() < 1e-4, "dim {}: {} vs {}", dim, fused, expected);
+            
+            let slices = DistanceMetric::Cosine.compute_slices(&a_data, &b_data).unwrap();
+            assert!((slices - expected).abs() < 1e-4, "dim {}: {} vs {}", dim, slices, expected);
+            
+            // The cached-norm path agrees too
+            let (mut a_cached, mut b_cached) = (a.clone(), b.clone());
+            a_cached.norm();
+            b_cached.norm();
+            let cached = DistanceMetric::Cosine.compute(&a_cached, &b_cached).unwrap();
+            assert!((cached - expected).abs() < 1e-4, "dim {}: {} vs {}", dim, cached, expected);
+        }
+        
+        let zero = Vector::new("z", vec![0.0; 16]).unwrap();
+        let one = Vector::new("o", vec![1.0; 16]).unwrap();
+        assert_eq!(DistanceMetric::Cosine.compute(&zero, &one).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn test_tanimoto_distance() {
+        let fingerprint = Vector::new("f", vec![1.0, 0.0, 1.0, 1.0, 0.0, 0.5]).unwrap();
+        assert!(DistanceMetric::Tanimoto.compute(&fingerprint, &fingerprint.clone()).unwrap().abs() < 1e-6);
+        
+        // No shared nonzero dimensions means no overlap at all
+        let a = Vector::new("a", vec![1.0, 2.0, 0.0, 0.0]).unwrap();
+        let b = Vector::new("b", vec![0.0, 0.0, 3.0, 0.5]).unwrap();
+        assert_eq!(DistanceMetric::Tanimoto.compute(&a, &b).unwrap(), 1.0);
+        
+        // On binary vectors it matches Jaccard: |{0}| / |{0, 1}| shared
+        let x = Vector::new("x", vec![1.0, 1.0, 0.0]).unwrap();
+        let y = Vector::new("y", vec![1.0, 0.0, 0.0]).unwrap();
+        assert!((DistanceMetric::Tanimoto.compute(&x, &y).unwrap() - 0.5).abs() < 1e-6);
+        assert!((DistanceMetric::Tanimoto.compute_slices(x.data(), y.data()).unwrap() - 0.5).abs() < 1e-6);
+        
+        let zero = Vector::new("zero", vec![0.0; 4]).unwrap();
+        assert_eq!(DistanceMetric::Tanimoto.compute(&zero, &zero).unwrap(), 1.0);
+        assert_eq!(DistanceMetric::Tanimoto.compute(&zero, &a).unwrap(), 1.0);
+        assert_eq!(format!("{:?}", DistanceMetric::Tanimoto), "Tanimoto");
+    }
+}
@@ -0,0 +1,32 @@
+#[cfg(test)]
+mod tests {
+    use crate::{recall_at_k, mean_recall_at_k};
+
+    fn results(ids: &[&str]) -> Vec<(String, f32)> {
+        ids.iter().enumerate().map(|(i, id)| (id.to_string(), i as f32)).collect()
+    }
+
+    #[test]
+    fn test_recall_at_k() {
+        let exact = results(&["a", "b", "c", "d"]);
+        assert_eq!(recall_at_k(&exact, &exact, 4), 1.0);
+        assert_eq!(recall_at_k(&exact, &results(&["w", "x", "y", "z"]), 4), 0.0);
+        
+        // Order within the top-k doesn't matter, but entries beyond k do not count
+        assert_eq!(recall_at_k(&exact, &results(&["d", "c", "b", "a"]), 4), 1.0);
+        assert_eq!(recall_at_k(&exact, &results(&["a", "x", "b"]), 2), 0.5);
+        
+        // Fewer exact results than k
+        assert_eq!(recall_at_k(&results(&["a", "b"]), &results(&["b", "z", "a"]), 10), 1.0);
+        assert_eq!(recall_at_k(&[], &results(&["a"]), 5), 1.0);
+    }
+
+    #[test]
+    fn test_mean_recall_at_k() {
+        let exact = vec![results(&["a", "b"]), results(&["c", "d"])];
+        let approx = vec![results(&["a", "b"]), results(&["x", "y"])];
+        assert_eq!(mean_recall_at_k(&exact, &exact, 2), 1.0);
+        assert_eq!(mean_recall_at_k(&exact, &approx, 2), 0.5);
+        assert_eq!(mean_recall_at_k(&[], &[], 2), 0.0);
+    }
+}
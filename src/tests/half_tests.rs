@@ -0,0 +1,60 @@
+#[cfg(all(test, feature = "f16"))]
+mod tests {
+    use crate::{Vector, DistanceMetric, HalfVector, ZyphyrError};
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_half_round_trip_precision() {
+        let values = [0.0f32, 1.0, -2.5, 0.333_333, 1024.0, 65504.0, -0.000_061];
+        let v = HalfVector::from_f32_slice("h", &values).unwrap();
+        assert_eq!(v.dim(), values.len());
+        
+        for (original, restored) in values.iter().zip(v.to_f32()) {
+            // f16 has an 11-bit significand, so relative error is at most 2^-11
+            let tolerance = original.abs() * 2f32.powi(-11) + 1e-7;
+            assert!((original - restored).abs() <= tolerance, "{} -> {}", original, restored);
+        }
+        
+        assert!(matches!(
+            HalfVector::from_f32_slice("empty", &[]),
+            Err(ZyphyrError::InvalidDimension { expected: 1, got: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_half_rejects_unrepresentable_values() {
+        // Finite in f32 but past the f16 maximum, so it would narrow to infinity
+        for bad in [f32::NAN, f32::INFINITY, 70000.0, -1e6] {
+            assert!(
+                matches!(HalfVector::from_f32_slice("h", &[1.0, bad]), Err(ZyphyrError::InvalidData(_))),
+                "{} accepted", bad
+            );
+        }
+        
+        // Values that round down to the maximum are kept
+        let edge = HalfVector::from_f32_slice("edge", &[65504.0, -65510.0]).unwrap();
+        assert_eq!(edge.to_f32(), vec![65504.0, -65504.0]);
+    }
+
+    #[test]
+    fn test_half_distances_match_f32() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let dim = 256;
+        let a: Vec<f32> = (0..dim).map(|_| rng.random_range(-1.0..1.0)).collect();
+        let b: Vec<f32> = (0..dim).map(|_| rng.random_range(-1.0..1.0)).collect();
+        
+        let full_a = Vector::from_slice("a", &a).unwrap();
+        let full_b = Vector::from_slice("b", &b).unwrap();
+        let half_a = HalfVector::from_f32_slice("a", &a).unwrap();
+        let half_b = HalfVector::from_f32_slice("b", &b).unwrap();
+        
+        for metric in [DistanceMetric::Euclidean, DistanceMetric::Cosine, DistanceMetric::DotProduct] {
+            let exact = metric.compute(&full_a, &full_b).unwrap();
+            let approx = half_a.distance_to(&half_b, metric.clone()).unwrap();
+            assert!((exact - approx).abs() < 1e-2, "{:?}: {} vs {}", metric, exact, approx);
+        }
+        
+        assert!(half_a.memory_usage() < full_a.memory_usage());
+    }
+}
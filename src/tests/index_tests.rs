@@ -0,0 +1,445 @@
+#[cfg(test)]
+mod tests {
+    use crate::{Vector, VectorCollection, DistanceMetric, HnswIndex, HnswPqIndex, IvfIndex, LshIndex};
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+
+    fn random_vectors(count: usize, dim: usize, seed: u64) -> Vec<Vector> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..count)
+            .map(|i| {
+                let data: Vec<f32> = (0..dim).map(|_| rng.random_range(-1.0..1.0)).collect();
+                Vector::new(format!("v{}", i), data).unwrap()
+            })
+            .collect()
+    }
+
+    // Points scattered uniformly in small boxes around `clusters` random centers
+    fn clustered_vectors(count: usize, clusters: usize, dim: usize, seed: u64) -> Vec<Vector> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let centers: Vec<Vec<f32>> = (0..clusters)
+            .map(|_| (0..dim).map(|_| rng.random_range(-10.0..10.0)).collect())
+            .collect();
+        (0..count)
+            .map(|i| {
+                let center = &centers[i % clusters];
+                let data: Vec<f32> = center.iter().map(|c| c + rng.random_range(-1.0..1.0)).collect();
+                Vector::new(format!("v{}", i), data).unwrap()
+            })
+            .collect()
+    }
+
+    fn recall(exact: &[(String, f32)], approx: &[(String, f32)]) -> f32 {
+        let hits = approx.iter().filter(|(id, _)| exact.iter().any(|(e, _)| e == id)).count();
+        hits as f32 / exact.len() as f32
+    }
+
+    #[test]
+    fn test_hnsw_recall_against_brute_force() {
+        let dim = 64;
+        let vectors = random_vectors(2000, dim, 42);
+        let queries = random_vectors(50, dim, 7);
+        
+        let mut collection = VectorCollection::new();
+        collection.batch_insert(vectors.clone()).unwrap();
+        let index = HnswIndex::build(&vectors, 16, 100, DistanceMetric::Euclidean, 42).unwrap();
+        assert_eq!(index.len(), 2000);
+        
+        let k = 10;
+        let mut hits = 0;
+        for query in &queries {
+            let exact = collection.search(query, k, DistanceMetric::Euclidean).unwrap();
+            let approx = index.search(query, k, 100).unwrap();
+            assert_eq!(approx.len(), k);
+            hits += approx.iter().filter(|(id, _)| exact.iter().any(|(e, _)| e == id)).count();
+        }
+        
+        let recall = hits as f32 / (queries.len() * k) as f32;
+        assert!(recall >= 0.9, "recall@10 too low: {}", recall);
+    }
+
+    #[test]
+    fn test_hnsw_empty_and_dimension_checks() {
+        let index = HnswIndex::build(&[], 16, 100, DistanceMetric::Euclidean, 42).unwrap();
+        assert!(index.is_empty());
+        let query = Vector::new("q", vec![1.0, 0.0]).unwrap();
+        assert!(index.search(&query, 5, 50).unwrap().is_empty());
+        
+        let vectors = random_vectors(20, 4, 1);
+        let index = HnswIndex::build(&vectors, 4, 20, DistanceMetric::Euclidean, 42).unwrap();
+        assert!(index.search(&query, 5, 50).is_err());
+        
+        // An exact match should come back first with zero distance
+        let results = index.search(&vectors[3], 1, 20).unwrap();
+        assert_eq!(results[0].0, "v3");
+        assert!(results[0].1.abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_hnsw_incremental_insert_matches_batch_recall() {
+        let dim = 32;
+        let vectors = random_vectors(1000, dim, 44);
+        let queries = random_vectors(30, dim, 45);
+        
+        let mut collection = VectorCollection::new();
+        collection.batch_insert(vectors.clone()).unwrap();
+        let batch = HnswIndex::build(&vectors, 12, 80, DistanceMetric::Euclidean, 42).unwrap();
+        
+        let mut incremental = HnswIndex::build(&[], 12, 80, DistanceMetric::Euclidean, 42).unwrap();
+        for v in &vectors {
+            incremental.insert(v.clone()).unwrap();
+        }
+        assert_eq!(incremental.len(), 1000);
+        assert!(incremental.insert(vectors[0].clone()).is_err());
+        assert!(incremental.insert(Vector::new("bad", vec![0.0; dim + 1]).unwrap()).is_err());
+        
+        let k = 10;
+        let (mut batch_recall, mut incremental_recall) = (0.0, 0.0);
+        for query in &queries {
+            let exact = collection.search(query, k, DistanceMetric::Euclidean).unwrap();
+            batch_recall += recall(&exact, &batch.search(query, k, 80).unwrap());
+            incremental_recall += recall(&exact, &incremental.search(query, k, 80).unwrap());
+        }
+        batch_recall /= queries.len() as f32;
+        incremental_recall /= queries.len() as f32;
+        
+        assert!(incremental_recall >= 0.9, "incremental recall too low: {}", incremental_recall);
+        assert!(
+            (batch_recall - incremental_recall).abs() <= 0.05,
+            "recall@10 batch {} vs incremental {}", batch_recall, incremental_recall
+        );
+    }
+
+    #[test]
+    fn test_hnsw_insert_into_empty_index() {
+        let mut index = HnswIndex::build(&[], 4, 20, DistanceMetric::Euclidean, 42).unwrap();
+        let first = Vector::new("first", vec![1.0, 2.0]).unwrap();
+        index.insert(first.clone()).unwrap();
+        
+        let results = index.search(&first, 5, 10).unwrap();
+        assert_eq!(results, vec![("first".to_string(), 0.0)]);
+    }
+
+    #[test]
+    fn test_hnsw_remove() {
+        let dim = 32;
+        let vectors = random_vectors(1000, dim, 46);
+        let queries = random_vectors(30, dim, 47);
+        let mut index = HnswIndex::build(&vectors, 12, 80, DistanceMetric::Euclidean, 42).unwrap();
+        let mut collection = VectorCollection::new();
+        collection.batch_insert(vectors.clone()).unwrap();
+        
+        // A stored vector is its own nearest neighbor until it is removed
+        assert_eq!(index.search(&vectors[5], 1, 50).unwrap()[0].0, "v5");
+        assert!(index.remove("v5").unwrap());
+        assert!(!index.remove("v5").unwrap());
+        collection.remove("v5");
+        let results = index.search(&vectors[5], 10, 50).unwrap();
+        assert!(results.iter().all(|(id, _)| id != "v5"));
+        
+        // Remove a tenth of the graph and check recall on what's left
+        for i in (0..1000).step_by(10) {
+            let id = format!("v{}", i);
+            index.remove(&id).unwrap();
+            collection.remove(&id);
+        }
+        assert_eq!(index.len(), collection.len());
+        
+        let k = 10;
+        let mut total = 0.0;
+        for query in &queries {
+            let exact = collection.search(query, k, DistanceMetric::Euclidean).unwrap();
+            let approx = index.search(query, k, 80).unwrap();
+            assert!(approx.iter().all(|(id, _)| collection.contains(id)));
+            total += recall(&exact, &approx);
+        }
+        let after = total / queries.len() as f32;
+        assert!(after >= 0.9, "recall after removals too low: {}", after);
+        
+        // Removed ids can be inserted again
+        index.insert(vectors[5].clone()).unwrap();
+        assert_eq!(index.search(&vectors[5], 1, 50).unwrap()[0].0, "v5");
+        
+        assert!(!index.rebuild_if_needed(0.5).unwrap());
+        assert!(index.deleted_fraction() > 0.05);
+        assert!(index.rebuild_if_needed(0.05).unwrap());
+        assert_eq!(index.deleted_fraction(), 0.0);
+        assert_eq!(index.len(), collection.len() + 1);
+    }
+
+    #[test]
+    fn test_hnsw_remove_top_candidates_keeps_k_results() {
+        let dim = 16;
+        let vectors = random_vectors(1000, dim, 49);
+        let mut index = HnswIndex::build(&vectors, 8, 60, DistanceMetric::Euclidean, 42).unwrap();
+        let mut collection = VectorCollection::new();
+        collection.batch_insert(vectors.clone()).unwrap();
+        
+        // Remove the 40 true nearest neighbors of the query, far more than the beam width
+        let query = &vectors[0];
+        for (id, _) in collection.search(query, 40, DistanceMetric::Euclidean).unwrap() {
+            assert!(index.remove(&id).unwrap());
+            collection.remove(&id);
+        }
+        
+        let k = 10;
+        let exact = collection.search(query, k, DistanceMetric::Euclidean).unwrap();
+        let approx = index.search(query, k, k).unwrap();
+        assert_eq!(approx.len(), k);
+        assert!(approx.iter().all(|(id, _)| collection.contains(id)));
+        let recall = recall(&exact, &approx);
+        assert!(recall >= 0.8, "recall after removing top candidates too low: {}", recall);
+    }
+
+    #[test]
+    fn test_hnsw_remove_everything() {
+        let vectors = random_vectors(20, 4, 48);
+        let mut index = HnswIndex::build(&vectors, 4, 20, DistanceMetric::Euclidean, 42).unwrap();
+        for v in &vectors {
+            assert!(index.remove(v.id()).unwrap());
+        }
+        assert!(index.is_empty());
+        assert!(index.search(&vectors[0], 5, 20).unwrap().is_empty());
+        
+        index.insert(vectors[3].clone()).unwrap();
+        assert_eq!(index.search(&vectors[0], 5, 20).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_ivf_recall_improves_with_nprobe() {
+        let dim = 16;
+        let vectors = clustered_vectors(5000, 40, dim, 21);
+        let queries = clustered_vectors(20, 40, dim, 22);
+        
+        let mut collection = VectorCollection::new();
+        collection.batch_insert(vectors.clone()).unwrap();
+        let nlist = 32;
+        let index = IvfIndex::train(&vectors, nlist, DistanceMetric::Euclidean, 42).unwrap();
+        assert_eq!(index.nlist(), nlist);
+        assert_eq!(index.len(), 5000);
+        
+        let mut previous = 0.0;
+        for nprobe in [1, 2, 4, 8, nlist] {
+            let mut total = 0.0;
+            for query in &queries {
+                let exact = collection.search(query, 10, DistanceMetric::Euclidean).unwrap();
+                let approx = index.search(query, 10, nprobe).unwrap();
+                total += recall(&exact, &approx);
+            }
+            let mean = total / queries.len() as f32;
+            assert!(mean >= previous, "recall@10 dropped at nprobe={}: {} < {}", nprobe, mean, previous);
+            previous = mean;
+        }
+        // Probing every list is an exhaustive search
+        assert!((previous - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_ivf_rejects_invalid_parameters() {
+        let vectors = random_vectors(10, 4, 2);
+        assert!(IvfIndex::train(&vectors, 0, DistanceMetric::Euclidean, 42).is_err());
+        assert!(IvfIndex::train(&vectors, 11, DistanceMetric::Euclidean, 42).is_err());
+        
+        let index = IvfIndex::train(&vectors, 3, DistanceMetric::Euclidean, 42).unwrap();
+        let query = Vector::new("q", vec![1.0, 2.0]).unwrap();
+        assert!(matches!(
+            index.search(&query, 1, 1),
+            Err(crate::ZyphyrError::InvalidDimension { expected: 4, got: 2 })
+        ));
+        assert!(index.assign(&vectors[0]).unwrap() < 3);
+    }
+
+    #[test]
+    fn test_lsh_recall_improves_with_tables() {
+        let dim = 32;
+        let vectors = random_vectors(2000, dim, 11);
+        let queries = random_vectors(50, dim, 12);
+        
+        let mut collection = VectorCollection::new();
+        collection.batch_insert(vectors.clone()).unwrap();
+        
+        let k = 10;
+        let exact: Vec<_> = queries
+            .iter()
+            .map(|q| collection.search(q, k, DistanceMetric::Euclidean).unwrap())
+            .collect();
+        
+        let mut recalls = Vec::new();
+        for num_tables in [1, 4, 16] {
+            let index = LshIndex::build(&vectors, num_tables, 6, DistanceMetric::Euclidean, 42).unwrap();
+            assert_eq!(index.len(), 2000);
+            let total: f32 = queries
+                .iter()
+                .zip(exact.iter())
+                .map(|(q, e)| recall(e, &index.query(q, k).unwrap()))
+                .sum();
+            recalls.push(total / queries.len() as f32);
+        }
+        
+        assert!(recalls[0] < recalls[1] && recalls[1] < recalls[2], "recall did not improve: {:?}", recalls);
+        assert!(recalls[2] >= 0.5, "recall@10 with 16 tables too low: {:?}", recalls);
+    }
+
+    #[test]
+    fn test_lsh_rejects_invalid_parameters() {
+        let vectors = random_vectors(10, 4, 3);
+        assert!(LshIndex::build(&vectors, 0, 8, DistanceMetric::Euclidean, 42).is_err());
+        assert!(LshIndex::build(&vectors, 2, 0, DistanceMetric::Euclidean, 42).is_err());
+        assert!(LshIndex::build(&vectors, 2, 65, DistanceMetric::Euclidean, 42).is_err());
+        
+        let index = LshIndex::build(&vectors, 2, 4, DistanceMetric::Euclidean, 42).unwrap();
+        let query = Vector::new("q", vec![1.0, 0.0]).unwrap();
+        assert!(index.query(&query, 5).is_err());
+        
+        // A stored vector always lands in its own buckets
+        let results = index.query(&vectors[5], 1).unwrap();
+        assert_eq!(results[0].0, "v5");
+    }
+
+    #[test]
+    fn test_hnsw_pq_reranking_improves_recall() {
+        let dim = 32;
+        let vectors = random_vectors(3000, dim, 72);
+        let queries = random_vectors(30, dim, 73);
+        let mut collection = VectorCollection::new();
+        collection.batch_insert(vectors.clone()).unwrap();
+        
+        let index = HnswPqIndex::build(&vectors, 8, 40, 8, 4, true, 42).unwrap();
+        assert_eq!(index.len(), 3000);
+        assert!(index.has_originals());
+        assert_eq!(index.quantizer().m(), 8);
+        
+        let k = 10;
+        let (mut plain, mut reranked) = (0.0, 0.0);
+        for query in &queries {
+            let exact = collection.search(query, k, DistanceMetric::Euclidean).unwrap();
+            let approx = index.search(query, k, 80, 0).unwrap();
+            assert_eq!(approx.len(), k);
+            plain += recall(&exact, &approx);
+            
+            let rescored = index.search(query, k, 80, 80).unwrap();
+            // Reranked distances are exact
+            let top = collection.get(&rescored[0].0).unwrap();
+            assert!((query.distance_to(top, DistanceMetric::Euclidean).unwrap() - rescored[0].1).abs() < 1e-4);
+            reranked += recall(&exact, &rescored);
+        }
+        plain /= queries.len() as f32;
+        reranked /= queries.len() as f32;
+        
+        assert!(reranked > plain, "reranking did not help: {} vs {}", reranked, plain);
+        assert!(reranked >= 0.7, "reranked recall too low: {}", reranked);
+    }
+
+    #[test]
+    fn test_hnsw_pq_without_originals() {
+        let vectors = random_vectors(300, 16, 74);
+        let index = HnswPqIndex::build(&vectors, 8, 40, 4, 4, false, 42).unwrap();
+        assert!(!index.has_originals());
+        
+        // Without originals, rerank_k is ignored and PQ distances are returned
+        let results = index.search(&vectors[0], 5, 40, 20).unwrap();
+        assert_eq!(results, index.search(&vectors[0], 5, 40, 0).unwrap());
+        assert!(index.search(&Vector::new("q", vec![0.0; 8]).unwrap(), 5, 40, 0).is_err());
+        
+        assert!(HnswPqIndex::build(&vectors, 1, 40, 4, 4, false, 42).is_err());
+        assert!(HnswPqIndex::build(&[], 8, 40, 4, 4, false, 42).is_err());
+    }
+
+    #[test]
+    fn test_build_progress_callback() {
+        let vectors = random_vectors(500, 8, 75);
+        let mut calls = Vec::new();
+        let index = HnswIndex::build_with_progress(&vectors, 8, 40, DistanceMetric::Euclidean, 42, |done, total| {
+            calls.push((done, total));
+        })
+        .unwrap();
+        assert_eq!(index.len(), 500);
+        assert!(calls.len() > 1);
+        assert_eq!(*calls.last().unwrap(), (500, 500));
+        assert!(calls.windows(2).all(|w| w[0].0 < w[1].0));
+        
+        let mut pq_calls = Vec::new();
+        HnswPqIndex::build_with_progress(&vectors, 8, 40, 2, 4, false, 42, |done, total| pq_calls.push((done, total)))
+            .unwrap();
+        assert_eq!(*pq_calls.last().unwrap(), (500, 500));
+        
+        let mut ivf_calls = Vec::new();
+        IvfIndex::train_with_progress(&vectors, 8, DistanceMetric::Euclidean, 42, |done, total| {
+            ivf_calls.push((done, total));
+        })
+        .unwrap();
+        let (_, ivf_total) = ivf_calls[0];
+        assert_eq!(*ivf_calls.last().unwrap(), (ivf_total, ivf_total));
+        assert!(ivf_calls.windows(2).all(|w| w[0].0 < w[1].0));
+        
+        let mut lsh_calls = Vec::new();
+        LshIndex::build_with_progress(&vectors, 4, 6, DistanceMetric::Euclidean, 42, |done, total| {
+            lsh_calls.push((done, total));
+        })
+        .unwrap();
+        assert_eq!(lsh_calls, vec![(1, 4), (2, 4), (3, 4), (4, 4)]);
+        
+        // An empty build still reports completion once
+        let mut empty_calls = Vec::new();
+        HnswIndex::build_with_progress(&[], 8, 40, DistanceMetric::Euclidean, 42, |done, total| {
+            empty_calls.push((done, total));
+        })
+        .unwrap();
+        assert_eq!(empty_calls, vec![(0, 0)]);
+    }
+
+    #[test]
+    fn test_hnsw_seeded_build_is_deterministic() {
+        let vectors = random_vectors(500, 16, 90);
+        let first = HnswIndex::build(&vectors, 8, 40, DistanceMetric::Euclidean, 7).unwrap();
+        let second = HnswIndex::build(&vectors, 8, 40, DistanceMetric::Euclidean, 7).unwrap();
+        assert_eq!(first.graph(), second.graph());
+        
+        // Inserting one at a time continues the same level sequence as a batch build
+        let mut incremental = HnswIndex::build(&[], 8, 40, DistanceMetric::Euclidean, 7).unwrap();
+        for v in &vectors {
+            incremental.insert(v.clone()).unwrap();
+        }
+        assert_eq!(incremental.graph(), first.graph());
+        
+        let other_seed = HnswIndex::build(&vectors, 8, 40, DistanceMetric::Euclidean, 8).unwrap();
+        assert_ne!(other_seed.graph(), first.graph());
+        
+        let query = &vectors[3];
+        assert_eq!(first.search(query, 10, 40).unwrap(), second.search(query, 10, 40).unwrap());
+    }
+
+    #[test]
+    fn test_hnsw_build_auto_meets_recall() {
+        let dim = 16;
+        let vectors = random_vectors(2000, dim, 98);
+        let index = HnswIndex::build_auto(&vectors, 0.95, DistanceMetric::Euclidean, 42).unwrap();
+        assert_eq!(index.len(), 2000);
+        // The target is reachable here, so the search shouldn't fall back to the ceiling
+        assert!(index.ef_search() >= 10 && index.ef_search() < 512, "ef_search {}", index.ef_search());
+        
+        let mut collection = VectorCollection::new();
+        collection.batch_insert(vectors.clone()).unwrap();
+        let queries = random_vectors(50, dim, 99);
+        let measured = queries
+            .iter()
+            .map(|q| {
+                let exact = collection.search(q, 10, DistanceMetric::Euclidean).unwrap();
+                recall(&exact, &index.search_default(q, 10).unwrap())
+            })
+            .sum::<f32>() / queries.len() as f32;
+        assert!(measured >= 0.9, "recall too low: {} at ef_search {}", measured, index.ef_search());
+        
+        // The seed fixes both the held-out sample and the graph
+        let subset = &vectors[..500];
+        let first = HnswIndex::build_auto(subset, 0.9, DistanceMetric::Euclidean, 7).unwrap();
+        let second = HnswIndex::build_auto(subset, 0.9, DistanceMetric::Euclidean, 7).unwrap();
+        assert_eq!(first.ef_search(), second.ef_search());
+        assert_eq!(first.graph(), second.graph());
+        
+        assert!(HnswIndex::build_auto(&vectors, 0.0, DistanceMetric::Euclidean, 42).is_err());
+        assert!(HnswIndex::build_auto(&vectors, 1.5, DistanceMetric::Euclidean, 42).is_err());
+        let tiny = HnswIndex::build_auto(&vectors[..1], 0.9, DistanceMetric::Euclidean, 42).unwrap();
+        assert_eq!(tiny.len(), 1);
+    }
+}
@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use crate::{Vector, VectorCollection, MmapCollection, DistanceMetric, ZyphyrError};
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("zyphyr_{}_{}.bin", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_csv_import() {
+        let csv = "id,x,y,z\ndoc1,1.0,2.0,3.0\ndoc2, -0.5, 0, 1e-3\n\ndoc3,4,5,6\n";
+        let collection = VectorCollection::from_csv_reader(csv.as_bytes(), true).unwrap();
+        
+        assert_eq!(collection.len(), 3);
+        assert_eq!(collection.dimensions(), Some(3));
+        assert_eq!(collection.get("doc2").unwrap().data(), &[-0.5, 0.0, 0.001]);
+        
+        // Without a header the first row is data
+        let collection = VectorCollection::from_csv_reader("a,1,2\nb,3,4".as_bytes(), false).unwrap();
+        assert_eq!(collection.len(), 2);
+        assert_eq!(collection.dimensions(), Some(2));
+    }
+
+    #[test]
+    fn test_csv_import_errors() {
+        let ragged = "a,1,2\nb,3,4,5\n";
+        assert!(matches!(
+            VectorCollection::from_csv_reader(ragged.as_bytes(), false),
+            Err(ZyphyrError::InvalidDimension { expected: 2, got: 3 })
+        ));
+        
+        let non_numeric = "a,1,two\n";
+        assert!(matches!(
+            VectorCollection::from_csv_reader(non_numeric.as_bytes(), false),
+            Err(ZyphyrError::Other(_))
+        ));
+        
+        let no_values = "a\n";
+        assert!(matches!(
+            VectorCollection::from_csv_reader(no_values.as_bytes(), false),
+            Err(ZyphyrError::InvalidDimension { expected: 1, got: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_mmap_collection_search() {
+        let vectors: Vec<Vector> = (0..100)
+            .map(|i| Vector::new(format!("v{}", i), vec![i as f32, (i % 7) as f32, 1.0]).unwrap())
+            .collect();
+        let path = temp_path("mmap_search");
+        MmapCollection::write_file(&path, &vectors).unwrap();
+        
+        let mmap = MmapCollection::open(&path).unwrap();
+        assert_eq!(mmap.len(), 100);
+        assert_eq!(mmap.dim(), 3);
+        assert_eq!(mmap.get_row(42), vectors[42].data());
+        
+        let mut collection = VectorCollection::new();
+        collection.batch_insert(vectors.clone()).unwrap();
+        let query = Vector::new("q", vec![30.2, 2.0, 1.0]).unwrap();
+        let expected = collection.search(&query, 5, DistanceMetric::Euclidean).unwrap();
+        let results = mmap.search(&query, 5, DistanceMetric::Euclidean).unwrap();
+        assert_eq!(results.len(), 5);
+        for ((row, distance), (id, want)) in results.iter().zip(&expected) {
+            assert_eq!(vectors[*row].id(), id);
+            assert!((distance - want).abs() < 1e-5);
+        }
+        
+        let wrong = Vector::new("w", vec![1.0, 2.0]).unwrap();
+        assert!(matches!(mmap.search(&wrong, 5, DistanceMetric::Euclidean), Err(ZyphyrError::InvalidDimension { .. })));
+        
+        drop(mmap);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_mmap_collection_length_mismatch() {
+        let vectors: Vec<Vector> = (0..4)
+            .map(|i| Vector::new(format!("v{}", i), vec![i as f32; 8]).unwrap())
+            .collect();
+        let path = temp_path("mmap_truncated");
+        MmapCollection::write_file(&path, &vectors).unwrap();
+        
+        // Chop off part of the last row
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &bytes[..bytes.len() - 6]).unwrap();
+        assert!(matches!(MmapCollection::open(&path), Err(ZyphyrError::Corrupt(_))));
+        
+        // Trailing garbage is rejected too
+        let mut padded = bytes.clone();
+        padded.extend_from_slice(&[0u8; 4]);
+        std::fs::write(&path, &padded).unwrap();
+        assert!(matches!(MmapCollection::open(&path), Err(ZyphyrError::Corrupt(_))));
+        
+        std::fs::write(&path, b"NOPE00000000000000000000").unwrap();
+        assert!(matches!(MmapCollection::open(&path), Err(ZyphyrError::Corrupt(_))));
+        
+        std::fs::remove_file(&path).unwrap();
+    }
+}
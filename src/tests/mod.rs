@@ -1 +1,15 @@
+// Index-based loops mirror the padded memory layout being checked
+#![allow(clippy::needless_range_loop)]
+
 mod vector_tests;
+mod distance_tests;
+mod collection_tests;
+mod quantization_tests;
+mod half_tests;
+mod serde_tests;
+mod io_tests;
+mod index_tests;
+mod transform_tests;
+mod sparse_tests;
+mod eval_tests;
+mod simd_tests;
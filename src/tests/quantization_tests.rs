@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod tests {
+    use crate::{Vector, VectorCollection, DistanceMetric, ProductQuantizer, quantized_euclidean, hamming_distance, ZyphyrError};
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+
+    fn random_vector(id: &str, dim: usize, rng: &mut StdRng) -> Vector {
+        let data: Vec<f32> = (0..dim).map(|_| rng.random_range(-5.0..5.0)).collect();
+        Vector::new(id, data).unwrap()
+    }
+
+    #[test]
+    fn test_scalar_quantization_reconstruction_error() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for i in 0..20 {
+            let v = random_vector(&format!("v{}", i), 256, &mut rng);
+            let q = v.quantize_scalar();
+            assert_eq!(q.id(), v.id());
+            assert_eq!(q.dim(), v.dim());
+            
+            let data = v.data();
+            let min = data.iter().cloned().fold(f32::INFINITY, f32::min);
+            let max = data.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+            let range = max - min;
+            
+            for (original, restored) in data.iter().zip(q.dequantize()) {
+                assert!((original - restored).abs() < 0.01 * range);
+            }
+        }
+    }
+
+    #[test]
+    fn test_scalar_quantization_memory_and_distance() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let a = random_vector("a", 512, &mut rng);
+        let b = random_vector("b", 512, &mut rng);
+        let qa = a.quantize_scalar();
+        let qb = b.quantize_scalar();
+        
+        assert!(
+            qa.memory_usage() * 3 < a.memory_usage(),
+            "f32: {} bytes, i8: {} bytes", a.memory_usage(), qa.memory_usage()
+        );
+        
+        let exact = DistanceMetric::Euclidean.compute(&a, &b).unwrap();
+        let approx = quantized_euclidean(&qa, &qb).unwrap();
+        assert!((exact - approx).abs() / exact < 0.01);
+        
+        let short = random_vector("short", 256, &mut rng).quantize_scalar();
+        assert!(matches!(
+            quantized_euclidean(&qa, &short),
+            Err(ZyphyrError::InvalidDimension { expected: 512, got: 256 })
+        ));
+    }
+
+    #[test]
+    fn test_scalar_quantization_constant_vector() {
+        let v = Vector::new("c", vec![3.5; 10]).unwrap();
+        let q = v.quantize_scalar();
+        assert!(q.codes().iter().all(|&c| c == 0));
+        assert_eq!(q.dequantize(), vec![3.5; 10]);
+    }
+
+    #[test]
+    fn test_binary_quantization_hamming() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let v = random_vector("v", 100, &mut rng);
+        let a = v.quantize_binary();
+        let b = v.quantize_binary();
+        assert_eq!(a.dim(), 100);
+        assert_eq!(a.bits().len(), 2);
+        assert_eq!(hamming_distance(&a, &b).unwrap(), 0);
+        
+        // Flip the sign of a single dimension
+        let mut flipped = v.data().to_vec();
+        flipped[70] = -flipped[70];
+        let c = Vector::new("c", flipped).unwrap().quantize_binary();
+        assert_eq!(hamming_distance(&a, &c).unwrap(), 1);
+        
+        assert!(a.memory_usage() < v.memory_usage());
+        
+        let short = Vector::new("short", vec![1.0; 64]).unwrap().quantize_binary();
+        assert!(matches!(
+            hamming_distance(&a, &short),
+            Err(ZyphyrError::InvalidDimension { expected: 100, got: 64 })
+        ));
+    }
+
+    #[test]
+    fn test_binary_quantization_ignores_padding() {
+        // All-negative vectors encode to zero bits, so a non-zero distance
+        // could only come from padding leaking into the popcount
+        let a = Vector::new("a", vec![-1.0; 3]).unwrap().quantize_binary();
+        let b = Vector::new("b", vec![-2.0; 3]).unwrap().quantize_binary();
+        assert_eq!(hamming_distance(&a, &b).unwrap(), 0);
+        
+        let c = Vector::new("c", vec![1.0; 3]).unwrap().quantize_binary();
+        assert_eq!(hamming_distance(&a, &c).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_product_quantization_top10_overlap() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let dim = 16;
+        let vectors: Vec<Vector> = (0..3000)
+            .map(|i| random_vector(&format!("v{}", i), dim, &mut rng))
+            .collect();
+        
+        let pq = ProductQuantizer::train(&vectors, 8, 5, 42).unwrap();
+        assert_eq!(pq.m(), 8);
+        assert_eq!(pq.ksub(), 32);
+        
+        let codes: Vec<_> = vectors.iter().map(|v| pq.encode(v).unwrap()).collect();
+        assert_eq!(codes[0].codes().len(), 8);
+        assert_eq!(pq.decode(&codes[0]).unwrap().len(), dim);
+        
+        // Codes from a quantizer with another m or nbits are rejected, not indexed blindly
+        let fewer_subspaces = ProductQuantizer::train(&vectors[..100], 4, 5, 42).unwrap();
+        assert!(matches!(fewer_subspaces.decode(&codes[0]), Err(ZyphyrError::InvalidDimension { expected: 4, got: 8 })));
+        let fewer_bits = ProductQuantizer::train(&vectors[..100], 8, 1, 42).unwrap();
+        let wide = codes.iter().find(|c| c.codes().iter().any(|&c| c > 1)).unwrap();
+        assert!(matches!(fewer_bits.decode(wide), Err(ZyphyrError::InvalidData(_))));
+        assert!(fewer_bits.search(&vectors[0], &codes, 5).is_err());
+        
+        let mut collection = VectorCollection::new();
+        collection.batch_insert(vectors).unwrap();
+        
+        let queries = 20;
+        let mut overlap = 0;
+        for q in 0..queries {
+            let query = random_vector(&format!("q{}", q), dim, &mut rng);
+            let exact = collection.search(&query, 10, DistanceMetric::Euclidean).unwrap();
+            let approx = pq.search(&query, &codes, 10).unwrap();
+            overlap += approx.iter().filter(|(id, _)| exact.iter().any(|(e, _)| e == id)).count();
+        }
+        let ratio = overlap as f32 / (queries * 10) as f32;
+        assert!(ratio >= 0.6, "PQ top-10 overlap too low: {}", ratio);
+    }
+
+    #[test]
+    fn test_product_quantization_rejects_invalid_parameters() {
+        let mut rng = StdRng::seed_from_u64(43);
+        let vectors: Vec<Vector> = (0..20).map(|i| random_vector(&format!("v{}", i), 6, &mut rng)).collect();
+        
+        assert!(ProductQuantizer::train(&[], 2, 2, 42).is_err());
+        assert!(ProductQuantizer::train(&vectors, 4, 2, 42).is_err());
+        assert!(ProductQuantizer::train(&vectors, 0, 2, 42).is_err());
+        assert!(ProductQuantizer::train(&vectors, 2, 0, 42).is_err());
+        assert!(ProductQuantizer::train(&vectors, 2, 9, 42).is_err());
+        // Not enough training vectors for 2^5 centroids
+        assert!(ProductQuantizer::train(&vectors, 2, 5, 42).is_err());
+        
+        let pq = ProductQuantizer::train(&vectors, 3, 2, 42).unwrap();
+        let wrong = Vector::new("w", vec![1.0; 4]).unwrap();
+        assert!(pq.encode(&wrong).is_err());
+        assert!(pq.distance_table(&wrong).is_err());
+    }
+}
@@ -0,0 +1,128 @@
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use crate::{Vector, VectorCollection, DistanceMetric, ZyphyrError};
+    use std::sync::Arc;
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut rng = StdRng::seed_from_u64(24);
+        let mut collection = VectorCollection::new();
+        for i in 0..5 {
+            let data: Vec<f32> = (0..1000).map(|_| rng.random_range(-1.0..1.0)).collect();
+            collection.insert(Vector::new(format!("doc-{}", i), data).unwrap()).unwrap();
+        }
+        
+        let json = collection.to_json().unwrap();
+        let restored = VectorCollection::from_json(&json).unwrap();
+        
+        assert_eq!(restored.len(), collection.len());
+        for original in collection.iter() {
+            let copy = restored.get(original.id()).unwrap();
+            assert_eq!(copy.dim(), 1000);
+            assert_eq!(copy.data(), original.data());
+        }
+    }
+
+    #[test]
+    fn test_json_format() {
+        let mut collection = VectorCollection::new();
+        collection.insert(Vector::new("a", vec![1.0, 2.5]).unwrap()).unwrap();
+        assert_eq!(collection.to_json().unwrap(), r#"[{"id":"a","data":[1.0,2.5]}]"#);
+        
+        let parsed = VectorCollection::from_json(r#"[{"id": "b", "data": [3, 4]}]"#).unwrap();
+        assert_eq!(parsed.get("b").unwrap().data(), &[3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_json_errors() {
+        assert!(matches!(VectorCollection::from_json("not json"), Err(ZyphyrError::Serialization(_))));
+        assert!(matches!(
+            VectorCollection::from_json(r#"[{"id": "a", "data": [1]}, {"id": "b", "data": [1, 2]}]"#),
+            Err(ZyphyrError::InvalidDimension { expected: 1, got: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_vector_serde_round_trip() {
+        let original = Vector::new("v", vec![1.0, -2.5, 3.0, 4.0, 5.0]).unwrap();
+        let json = serde_json::to_string(&original).unwrap();
+        // The zero padding never reaches the wire
+        assert_eq!(json, r#"{"id":"v","data":[1.0,-2.5,3.0,4.0,5.0]}"#);
+        
+        let restored: Vector = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.id(), "v");
+        assert_eq!(restored.dim(), 5);
+        assert_eq!(restored.padded_dim(), original.padded_dim());
+        assert_eq!(restored.data(), original.data());
+        assert!(restored.raw_data()[5..].iter().all(|&x| x == 0.0));
+        assert!(restored.is_aligned());
+        
+        let tagged = original.with_metadata([("k".to_string(), "v".to_string())].into());
+        let restored: Vector = serde_json::from_str(&serde_json::to_string(&tagged).unwrap()).unwrap();
+        assert_eq!(restored.metadata().unwrap()["k"], "v");
+        
+        assert!(serde_json::from_str::<Vector>(r#"{"id":"e","data":[]}"#).is_err());
+    }
+
+    #[test]
+    fn test_distance_metric_serde() {
+        let weighted = DistanceMetric::WeightedEuclidean { weights: Arc::new(vec![1.0, 2.0]) };
+        for metric in [DistanceMetric::Cosine, DistanceMetric::Angular, weighted] {
+            let json = serde_json::to_string(&metric).unwrap();
+            let restored: DistanceMetric = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored, metric);
+        }
+        assert_eq!(serde_json::to_string(&DistanceMetric::Euclidean).unwrap(), r#""Euclidean""#);
+        
+        let custom = DistanceMetric::Custom(Arc::new(|_, _| 0.0));
+        assert!(serde_json::to_string(&custom).is_err());
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn test_bincode_round_trip() {
+        let mut rng = StdRng::seed_from_u64(69);
+        let mut collection = VectorCollection::new();
+        for i in 0..50 {
+            let data: Vec<f32> = (0..64).map(|_| rng.random_range(-1.0..1.0)).collect();
+            collection.insert(Vector::new(format!("doc-{}", i), data).unwrap()).unwrap();
+        }
+        let tagged = Vector::new("tagged", vec![0.5; 64]).unwrap()
+            .with_metadata([("source".to_string(), "test".to_string())].into());
+        collection.insert(tagged).unwrap();
+        collection.soft_remove("doc-3");
+        
+        let bytes = collection.to_bincode().unwrap();
+        let json = collection.to_json().unwrap();
+        assert!(bytes.len() < json.len(), "bincode {} bytes vs JSON {} bytes", bytes.len(), json.len());
+        
+        let restored = VectorCollection::from_bincode(&bytes).unwrap();
+        assert_eq!(restored.len(), collection.len());
+        assert!(!restored.contains("doc-3"));
+        for original in collection.iter() {
+            assert_eq!(restored.get(original.id()).unwrap().data(), original.data());
+        }
+        assert_eq!(restored.get("tagged").unwrap().metadata().unwrap()["source"], "test");
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn test_bincode_rejects_bad_headers() {
+        let mut collection = VectorCollection::new();
+        collection.insert(Vector::new("a", vec![1.0, 2.0]).unwrap()).unwrap();
+        let bytes = collection.to_bincode().unwrap();
+        
+        let mut wrong_magic = bytes.clone();
+        wrong_magic[0] = b'X';
+        assert!(matches!(VectorCollection::from_bincode(&wrong_magic), Err(ZyphyrError::Corrupt(_))));
+        
+        let mut wrong_version = bytes.clone();
+        wrong_version[4] = 99;
+        assert!(matches!(VectorCollection::from_bincode(&wrong_version), Err(ZyphyrError::Corrupt(_))));
+        
+        assert!(matches!(VectorCollection::from_bincode(&bytes[..bytes.len() - 1]), Err(ZyphyrError::Corrupt(_))));
+        assert!(matches!(VectorCollection::from_bincode(&[]), Err(ZyphyrError::Corrupt(_))));
+    }
+}
@@ -0,0 +1,36 @@
+#[cfg(test)]
+mod tests {
+    use crate::{SimdCapabilities, detect_simd, simd_support_info};
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn test_detect_simd_matches_feature_detection() {
+        let caps = detect_simd();
+        assert_eq!(caps.sse, std::arch::is_x86_feature_detected!("sse"));
+        assert_eq!(caps.avx, std::arch::is_x86_feature_detected!("avx"));
+        assert_eq!(caps.avx2, std::arch::is_x86_feature_detected!("avx2"));
+        assert_eq!(caps.avx512, std::arch::is_x86_feature_detected!("avx512f"));
+        assert!(!caps.neon);
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    #[test]
+    fn test_detect_simd_matches_feature_detection() {
+        let caps = detect_simd();
+        assert_eq!(caps.neon, std::arch::is_aarch64_feature_detected!("neon"));
+        assert!(!caps.avx && !caps.avx2 && !caps.avx512 && !caps.sse);
+    }
+
+    #[test]
+    fn test_simd_capabilities_display() {
+        assert_eq!(SimdCapabilities::default().to_string(), "No advanced SIMD features detected");
+        
+        let caps = SimdCapabilities { sse: true, avx: true, avx2: true, ..Default::default() };
+        assert_eq!(caps.to_string(), "AVX supported\nAVX2 supported\n");
+        
+        let sse_only = SimdCapabilities { sse: true, ..Default::default() };
+        assert_eq!(sse_only.to_string(), "No advanced SIMD features detected");
+        
+        assert_eq!(simd_support_info(), detect_simd().to_string());
+    }
+}
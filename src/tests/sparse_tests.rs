@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use crate::{SparseVector, SparseCollection, DistanceMetric, ZyphyrError};
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+
+    fn random_sparse(id: &str, dim: usize, nnz: usize, rng: &mut StdRng) -> SparseVector {
+        let mut indices: Vec<u32> = Vec::with_capacity(nnz);
+        while indices.len() < nnz {
+            let index = rng.random_range(0..dim as u32);
+            if !indices.contains(&index) {
+                indices.push(index);
+            }
+        }
+        indices.sort_unstable();
+        let values = (0..nnz).map(|_| rng.random_range(-1.0..1.0)).collect();
+        SparseVector::new(id, indices, values, dim).unwrap()
+    }
+
+    #[test]
+    fn test_sparse_dot_matches_dense() {
+        let mut rng = StdRng::seed_from_u64(40);
+        for _ in 0..20 {
+            let a = random_sparse("a", 300, 30, &mut rng);
+            let b = random_sparse("b", 300, 30, &mut rng);
+            let dense_a = a.to_dense().unwrap();
+            let dense_b = b.to_dense().unwrap();
+            
+            let sparse_dot = a.dot(&b).unwrap();
+            let dense_dot = DistanceMetric::DotProduct.compute(&dense_a, &dense_b).unwrap();
+            assert!((sparse_dot - dense_dot).abs() < 1e-4, "{} vs {}", sparse_dot, dense_dot);
+            
+            let sparse_cos = a.cosine_distance(&b).unwrap();
+            let dense_cos = DistanceMetric::Cosine.compute(&dense_a, &dense_b).unwrap();
+            assert!((sparse_cos - dense_cos).abs() < 1e-4, "{} vs {}", sparse_cos, dense_cos);
+        }
+    }
+
+    #[test]
+    fn test_sparse_validation() {
+        assert!(matches!(
+            SparseVector::new("v", vec![3, 1], vec![1.0, 2.0], 10),
+            Err(ZyphyrError::Other(_))
+        ));
+        assert!(matches!(
+            SparseVector::new("v", vec![1, 1], vec![1.0, 2.0], 10),
+            Err(ZyphyrError::Other(_))
+        ));
+        assert!(SparseVector::new("v", vec![1, 10], vec![1.0, 2.0], 10).is_err());
+        assert!(SparseVector::new("v", vec![1], vec![1.0, 2.0], 10).is_err());
+        
+        let v = SparseVector::new("v", vec![2, 7], vec![1.0, 2.0], 10).unwrap();
+        assert_eq!(v.nnz(), 2);
+        let other = SparseVector::new("o", vec![2], vec![1.0], 11).unwrap();
+        assert!(matches!(v.dot(&other), Err(ZyphyrError::InvalidDimension { expected: 10, got: 11 })));
+        
+        let empty = SparseVector::new("e", vec![], vec![], 10).unwrap();
+        assert_eq!(v.cosine_distance(&empty).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn test_sparse_collection_matches_brute_force() {
+        let mut rng = StdRng::seed_from_u64(41);
+        let dim = 1000;
+        let vectors: Vec<SparseVector> = (0..500)
+            .map(|i| random_sparse(&format!("v{}", i), dim, 20, &mut rng))
+            .collect();
+        
+        let mut collection = SparseCollection::new();
+        for v in &vectors {
+            collection.insert(v.clone()).unwrap();
+        }
+        assert_eq!(collection.len(), 500);
+        assert!(collection.insert(vectors[0].clone()).is_err());
+        
+        for q in 0..20 {
+            let query = random_sparse(&format!("q{}", q), dim, 50, &mut rng);
+            
+            let mut expected: Vec<(String, f32)> = vectors
+                .iter()
+                .filter(|v| v.indices().iter().any(|i| query.indices().contains(i)))
+                .map(|v| (v.id().to_string(), query.dot(v).unwrap()))
+                .collect();
+            expected.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap().then_with(|| a.0.cmp(&b.0)));
+            expected.truncate(10);
+            
+            let results = collection.search(&query, 10).unwrap();
+            assert_eq!(results.len(), expected.len());
+            for ((id, score), (want_id, want_score)) in results.iter().zip(&expected) {
+                assert_eq!(id, want_id);
+                assert!((score - want_score).abs() < 1e-5);
+            }
+        }
+        
+        let wrong_dim = SparseVector::new("q", vec![0], vec![1.0], dim + 1).unwrap();
+        assert!(collection.search(&wrong_dim, 10).is_err());
+    }
+}
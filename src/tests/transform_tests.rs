@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use crate::{Vector, VectorCollection, DistanceMetric, RandomProjector};
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_pca_preserves_distance_ordering_on_plane() {
+        // Points on the plane through (1, 2, 3) spanned by two non-axis directions
+        let origin = [1.0f32, 2.0, 3.0];
+        let u = [1.0f32, 1.0, 0.0];
+        let w = [0.0f32, 1.0, -1.0];
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut collection = VectorCollection::new();
+        for i in 0..40 {
+            let a: f32 = rng.random_range(-5.0..5.0);
+            let b: f32 = rng.random_range(-5.0..5.0);
+            let data: Vec<f32> = (0..3).map(|d| origin[d] + a * u[d] + b * w[d]).collect();
+            collection.insert(Vector::new(format!("p{}", i), data).unwrap()).unwrap();
+        }
+        
+        let pca = collection.fit_pca(2).unwrap();
+        assert_eq!(pca.input_dim(), 3);
+        assert_eq!(pca.output_dim(), 2);
+        
+        let original: Vec<&Vector> = collection.iter().collect();
+        let projected: Vec<Vector> = original.iter().map(|v| pca.transform(v)).collect();
+        assert_eq!(projected[0].id(), original[0].id());
+        assert_eq!(projected[0].dim(), 2);
+        
+        let mut pairs = Vec::new();
+        for i in 0..original.len() {
+            for j in (i + 1)..original.len() {
+                let before = original[i].distance_to(original[j], DistanceMetric::Euclidean).unwrap();
+                let after = projected[i].distance_to(&projected[j], DistanceMetric::Euclidean).unwrap();
+                assert!((before - after).abs() < 1e-3, "{} vs {}", before, after);
+                pairs.push((before, after));
+            }
+        }
+        
+        pairs.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        for window in pairs.windows(2) {
+            // Allow for float noise between nearly equal distances
+            assert!(window[0].1 <= window[1].1 + 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_pca_rejects_invalid_target() {
+        let empty = VectorCollection::new();
+        assert!(empty.fit_pca(1).is_err());
+        
+        let mut collection = VectorCollection::new();
+        collection.insert(Vector::new("a", vec![1.0, 0.0]).unwrap()).unwrap();
+        collection.insert(Vector::new("b", vec![0.0, 1.0]).unwrap()).unwrap();
+        assert!(collection.fit_pca(0).is_err());
+        assert!(collection.fit_pca(3).is_err());
+        
+        // Asking for more components than the data's rank still yields orthonormal axes
+        let pca = collection.fit_pca(2).unwrap();
+        let axes = pca.components();
+        let dot: f32 = axes[0].iter().zip(&axes[1]).map(|(a, b)| a * b).sum();
+        assert!(dot.abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_random_projection_preserves_distances() {
+        let (input_dim, output_dim) = (512, 128);
+        let projector = RandomProjector::new(input_dim, output_dim, 63).unwrap();
+        assert_eq!(projector.input_dim(), input_dim);
+        assert_eq!(projector.output_dim(), output_dim);
+        
+        let mut rng = StdRng::seed_from_u64(64);
+        let vectors: Vec<Vector> = (0..30)
+            .map(|i| {
+                let data: Vec<f32> = (0..input_dim).map(|_| rng.random_range(-1.0..1.0)).collect();
+                Vector::new(format!("v{}", i), data).unwrap()
+            })
+            .collect();
+        let projected: Vec<Vector> = vectors.iter().map(|v| projector.transform(v)).collect();
+        assert_eq!(projected[0].id(), "v0");
+        assert_eq!(projected[0].dim(), output_dim);
+        
+        let mut ratios = Vec::new();
+        for i in 0..vectors.len() {
+            for j in (i + 1)..vectors.len() {
+                let before = vectors[i].distance_to(&vectors[j], DistanceMetric::Euclidean).unwrap();
+                let after = projected[i].distance_to(&projected[j], DistanceMetric::Euclidean).unwrap();
+                ratios.push(after / before);
+            }
+        }
+        let mean = ratios.iter().sum::<f32>() / ratios.len() as f32;
+        assert!((mean - 1.0).abs() < 0.05, "mean ratio {}", mean);
+        assert!(ratios.iter().all(|r| (0.6..1.4).contains(r)));
+        
+        // The matrix is a pure function of the seed
+        let again = RandomProjector::new(input_dim, output_dim, 63).unwrap();
+        assert_eq!(again.transform(&vectors[0]).data(), projected[0].data());
+        assert!(RandomProjector::new(0, 4, 1).is_err());
+        assert!(RandomProjector::new(4, 0, 1).is_err());
+    }
+}
@@ -16,29 +16,24 @@ mod tests {
     }
 
     #[test]
-    fn test_vector_alignment_realistic() {
-        // Test multiple vectors to see alignment behavior
-        let mut aligned_count = 0;
-        let total_tests = 100;
-        
-        for i in 0..total_tests {
-            let v = Vector::new(format!("v{}", i), vec![1.0, 2.0, 3.0, 4.0]).unwrap();
-            let ptr = v.raw_data().as_ptr() as *const u8;
-            if is_aligned(ptr, SIMD_ALIGNMENT) {
-                aligned_count += 1;
-            }
+    fn test_vector_alignment_guaranteed() {
+        // Every constructor allocates through AlignedVec, so alignment is guaranteed,
+        // not a matter of allocator luck
+        for i in 0..100 {
+            let v = Vector::new(format!("v{}", i), vec![1.0; 1 + i % 37]).unwrap();
+            assert!(v.is_aligned(), "vector {} is misaligned", i);
+            assert!(is_aligned(v.raw_data().as_ptr() as *const u8, SIMD_ALIGNMENT));
         }
         
-        // We should get some alignment by chance, but not 100%
-        // This tests that our alignment detection works
-        println!("Aligned vectors: {}/{}", aligned_count, total_tests);
-        
-        // At minimum, test that our alignment detection function works
-        let test_ptr = 0x20 as *const u8; // 32-byte aligned address
-        assert!(is_aligned(test_ptr, SIMD_ALIGNMENT));
+        let base = Vector::new("base", vec![3.0, 4.0, 5.0]).unwrap();
+        assert!(Vector::new_normalized("n", vec![3.0, 4.0]).unwrap().is_aligned());
+        assert!(base.scale(2.0, "s").is_aligned());
+        assert!(base.add(&base, "sum").unwrap().is_aligned());
+        assert!(base.clone().is_aligned());
         
-        let test_ptr = 0x21 as *const u8; // Not aligned
-        assert!(!is_aligned(test_ptr, SIMD_ALIGNMENT));
+        // The alignment check itself distinguishes aligned from misaligned addresses
+        assert!(is_aligned(0x20 as *const u8, SIMD_ALIGNMENT));
+        assert!(!is_aligned(0x21 as *const u8, SIMD_ALIGNMENT));
     }
 
     #[test]
@@ -54,7 +49,7 @@ mod tests {
             
             // Padding should be correct
             let simd_width = get_simd_width();
-            let expected_padded = ((dim + simd_width - 1) / simd_width) * simd_width;
+            let expected_padded = dim.div_ceil(simd_width) * simd_width;
             assert_eq!(v.padded_dim(), expected_padded);
             
             // Padded data should contain original data + zeros
@@ -71,7 +66,7 @@ mod tests {
     #[test]
     fn test_batch_distance_computation() {
         let query = Vector::new("query", vec![1.0, 0.0]).unwrap();
-        let vectors = vec![
+        let vectors = [
             Vector::new("v1", vec![1.0, 0.0]).unwrap(),
             Vector::new("v2", vec![0.0, 1.0]).unwrap(),
             Vector::new("v3", vec![-1.0, 0.0]).unwrap(),
@@ -86,6 +81,35 @@ mod tests {
         assert!((distances[2] - 2.0).abs() < 1e-6); // Distance of 2
     }
 
+    #[test]
+    fn test_tiled_batch_distance_matches_naive() {
+        use rand::{Rng, SeedableRng};
+        use rand::rngs::StdRng;
+        
+        let mut rng = StdRng::seed_from_u64(89);
+        let mut random = |id: String| {
+            let data: Vec<f32> = (0..128).map(|_| rng.random_range(-1.0..1.0)).collect();
+            Vector::new(id, data).unwrap()
+        };
+        let query = random("query".to_string());
+        // Enough vectors to span many tiles and take the parallel path
+        let vectors: Vec<Vector> = (0..3000).map(|i| random(format!("v{}", i))).collect();
+        let refs: Vec<&Vector> = vectors.iter().collect();
+        
+        for metric in [DistanceMetric::Euclidean, DistanceMetric::Cosine, DistanceMetric::DotProduct] {
+            let naive: Vec<f32> = refs.iter().map(|v| metric.compute(&query, v).unwrap()).collect();
+            assert_eq!(query.batch_distance(&refs, metric.clone()).unwrap(), naive);
+            assert_eq!(query.batch_distance(&refs[..7], metric).unwrap(), naive[..7]);
+        }
+        assert!(query.batch_distance(&[], DistanceMetric::Euclidean).unwrap().is_empty());
+        
+        // A mismatched vector anywhere in the batch fails the whole call
+        let odd = Vector::new("odd", vec![1.0; 3]).unwrap();
+        let mut mixed = refs.clone();
+        mixed[2500] = &odd;
+        assert!(query.batch_distance(&mixed, DistanceMetric::Euclidean).is_err());
+    }
+
     #[test]
     fn test_memory_usage_accuracy() {
         let v = Vector::new("test_vector", vec![1.0; 100]).unwrap();
@@ -364,15 +388,9 @@ mod tests {
 
     #[test]
     fn test_proper_simd_alignment_with_aligned_vec() {
-        // This test verifies that we can achieve proper SIMD alignment
-        // when we use AlignedVec instead of standard Box allocation
-        
-        // Note: This test is for the future aligned implementation
-        // The current implementation uses standard Box allocation which
-        // doesn't guarantee SIMD alignment but provides the interface
-        // for when we upgrade to aligned allocation
-        
+        // Vectors are backed by AlignedVec, so the buffer is always SIMD aligned
         let v = Vector::new("aligned_test", vec![1.0; 64]).unwrap();
+        assert!(v.is_aligned());
         
         // Test the key properties that must work regardless of alignment
         assert_eq!(v.dim(), 64);
@@ -397,7 +415,7 @@ mod tests {
 
     #[test]
     fn test_realistic_alignment_behavior() {
-        // Test what actually happens with Box allocation
+        // Test what actually happens with AlignedVec allocation
         let test_size = 100;
         let mut alignment_stats = std::collections::HashMap::new();
         
@@ -410,13 +428,374 @@ mod tests {
         
         println!("Alignment distribution: {:?}", alignment_stats);
         
-        // We should see various alignment values, showing that
-        // standard allocation doesn't guarantee SIMD alignment
-        assert!(alignment_stats.len() > 1, "Should have varied alignment with Box allocation");
+        // AlignedVec allocates on SIMD_ALIGNMENT boundaries, so every
+        // vector should land on offset zero
+        assert_eq!(alignment_stats.len(), 1, "AlignedVec allocation should have uniform alignment");
+        assert_eq!(alignment_stats.get(&0), Some(&test_size));
         
-        // But our padding should still work correctly
+        // And our padding should still work correctly
         let v = Vector::new("test", vec![1.0, 2.0, 3.0]).unwrap();
         assert!(v.padded_dim() >= v.dim());
         assert_eq!(v.padded_dim() % get_simd_width(), 0);
     }
-}
\ No newline at end of file
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn test_euclidean_avx2_matches_scalar() {
+        use crate::vector::distance::{euclidean_distance, euclidean_distance_avx2};
+        use rand::{Rng, SeedableRng};
+        use rand::rngs::StdRng;
+        
+        if !std::arch::is_x86_feature_detected!("avx2") {
+            println!("AVX2 not available, skipping");
+            return;
+        }
+        
+        let mut rng = StdRng::seed_from_u64(7);
+        let dim = 512;
+        let a: Vec<f32> = (0..dim).map(|_| rng.random_range(-1.0..1.0)).collect();
+        let b: Vec<f32> = (0..dim).map(|_| rng.random_range(-1.0..1.0)).collect();
+        let v1 = Vector::new("a", a).unwrap();
+        let v2 = Vector::new("b", b).unwrap();
+        
+        let scalar = euclidean_distance(v1.data(), v2.data());
+        let simd = unsafe { euclidean_distance_avx2(v1.raw_data(), v2.raw_data()) };
+        assert!((scalar - simd).abs() < 1e-4, "scalar {} vs simd {}", scalar, simd);
+        
+        // The dispatched metric should agree as well
+        let dispatched = DistanceMetric::Euclidean.compute(&v1, &v2).unwrap();
+        assert!((scalar - dispatched).abs() < 1e-4);
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn test_dot_product_avx2_matches_scalar() {
+        use crate::vector::distance::{dot_product, dot_product_avx2};
+        use rand::{Rng, SeedableRng};
+        use rand::rngs::StdRng;
+        
+        if !std::arch::is_x86_feature_detected!("avx2") || !std::arch::is_x86_feature_detected!("fma") {
+            println!("AVX2/FMA not available, skipping");
+            return;
+        }
+        
+        let mut rng = StdRng::seed_from_u64(9);
+        let dim = 1024;
+        let a: Vec<f32> = (0..dim).map(|_| rng.random_range(-1.0..1.0)).collect();
+        let b: Vec<f32> = (0..dim).map(|_| rng.random_range(-1.0..1.0)).collect();
+        let v1 = Vector::new("a", a).unwrap();
+        let v2 = Vector::new("b", b).unwrap();
+        
+        let scalar = dot_product(v1.data(), v2.data());
+        let simd = unsafe { dot_product_avx2(v1.raw_data(), v2.raw_data()) };
+        assert!((scalar - simd).abs() < 1e-3, "scalar {} vs simd {}", scalar, simd);
+        
+        let dispatched = DistanceMetric::DotProduct.compute(&v1, &v2).unwrap();
+        assert!((scalar - dispatched).abs() < 1e-3);
+        
+        // Unpadded slices with a ragged tail go through the same kernel
+        let sliced = DistanceMetric::DotProduct.compute_slices(&v1.data()[..1001], &v2.data()[..1001]).unwrap();
+        assert!((dot_product(&v1.data()[..1001], &v2.data()[..1001]) - sliced).abs() < 1e-3);
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    #[test]
+    fn test_neon_kernels_match_scalar() {
+        use crate::vector::distance::{dot_product, dot_product_neon, euclidean_distance, euclidean_distance_neon};
+        use rand::{Rng, SeedableRng};
+        use rand::rngs::StdRng;
+        
+        if !std::arch::is_aarch64_feature_detected!("neon") {
+            println!("NEON not available, skipping");
+            return;
+        }
+        
+        let mut rng = StdRng::seed_from_u64(13);
+        let dim = 1023;
+        let a: Vec<f32> = (0..dim).map(|_| rng.random_range(-1.0..1.0)).collect();
+        let b: Vec<f32> = (0..dim).map(|_| rng.random_range(-1.0..1.0)).collect();
+        let v1 = Vector::new("a", a).unwrap();
+        let v2 = Vector::new("b", b).unwrap();
+        
+        let scalar = euclidean_distance(v1.data(), v2.data());
+        let simd = unsafe { euclidean_distance_neon(v1.raw_data(), v2.raw_data()) };
+        assert!((scalar - simd).abs() < 1e-4, "scalar {} vs simd {}", scalar, simd);
+        assert!((scalar - DistanceMetric::Euclidean.compute(&v1, &v2).unwrap()).abs() < 1e-4);
+        
+        let scalar = dot_product(v1.data(), v2.data());
+        let simd = unsafe { dot_product_neon(v1.raw_data(), v2.raw_data()) };
+        assert!((scalar - simd).abs() < 1e-3, "scalar {} vs simd {}", scalar, simd);
+        assert!((scalar - DistanceMetric::DotProduct.compute(&v1, &v2).unwrap()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_vector_metadata() {
+        let plain = Vector::new("v1", vec![1.0, 2.0]).unwrap();
+        assert!(plain.metadata().is_none());
+        
+        let mut metadata = std::collections::HashMap::new();
+        metadata.insert("source".to_string(), "https://example.com/doc1".to_string());
+        metadata.insert("timestamp".to_string(), "1700000000".to_string());
+        let tagged = Vector::new("v1", vec![1.0, 2.0]).unwrap().with_metadata(metadata);
+        
+        let stored = tagged.metadata().unwrap();
+        assert_eq!(stored.get("source").unwrap(), "https://example.com/doc1");
+        
+        // The metadata heap should be reflected in the memory estimate
+        assert!(tagged.memory_usage() > plain.memory_usage() + "https://example.com/doc1".len());
+    }
+
+    #[test]
+    fn test_distance_to_matches_compute() {
+        let v1 = Vector::new("v1", vec![1.0, 2.0, 3.0]).unwrap();
+        let v2 = Vector::new("v2", vec![4.0, 0.0, -1.0]).unwrap();
+        
+        for metric in [DistanceMetric::Euclidean, DistanceMetric::Cosine, DistanceMetric::DotProduct] {
+            let direct = metric.compute(&v1, &v2).unwrap();
+            assert_eq!(v1.distance_to(&v2, metric).unwrap(), direct);
+        }
+        
+        let v3 = Vector::new("v3", vec![1.0, 2.0]).unwrap();
+        assert!(v1.distance_to(&v3, DistanceMetric::Euclidean).is_err());
+    }
+
+    #[test]
+    fn test_cached_norm() {
+        let mut v = Vector::new("v", vec![3.0, 4.0]).unwrap();
+        let other = Vector::new("o", vec![1.0, 2.0]).unwrap();
+        assert!(v.cached_norm().is_none());
+        
+        let uncached = DistanceMetric::Cosine.compute(&v, &other).unwrap();
+        assert_eq!(v.norm(), 5.0);
+        assert_eq!(v.cached_norm(), Some(5.0));
+        let cached = DistanceMetric::Cosine.compute(&v, &other).unwrap();
+        assert_eq!(uncached, cached);
+        
+        // Normalizing changes the data, so the stale norm must be dropped
+        v.normalize();
+        assert!(v.cached_norm().is_none());
+        assert!((v.norm() - 1.0).abs() < 1e-6);
+        assert!((DistanceMetric::Cosine.compute(&v, &other).unwrap() - cached).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_collection_caches_norms_on_insert() {
+        let mut collection = VectorCollection::new();
+        collection.insert(Vector::new("v1", vec![3.0, 4.0]).unwrap()).unwrap();
+        assert_eq!(collection.get("v1").unwrap().cached_norm(), Some(5.0));
+        
+        let query = Vector::new("q", vec![1.0, 0.0]).unwrap();
+        let results = collection.search(&query, 1, DistanceMetric::Cosine).unwrap();
+        assert!((results[0].1 - 0.4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_vector_arithmetic() {
+        let a = Vector::new("a", vec![1.0, 2.0]).unwrap();
+        let b = Vector::new("b", vec![3.0, 4.0]).unwrap();
+        
+        let sum = a.add(&b, "sum").unwrap();
+        assert_eq!(sum.id(), "sum");
+        assert_eq!(sum.data(), &[4.0, 6.0]);
+        
+        let diff = b.sub(&a, "diff").unwrap();
+        assert_eq!(diff.data(), &[2.0, 2.0]);
+        
+        let scaled = a.scale(3.0, "scaled");
+        assert_eq!(scaled.data(), &[3.0, 6.0]);
+        
+        // Results are freshly padded with zeros
+        assert_eq!(sum.padded_dim(), a.padded_dim());
+        assert!(sum.raw_data()[sum.dim()..].iter().all(|&x| x == 0.0));
+        
+        let c = Vector::new("c", vec![1.0, 2.0, 3.0]).unwrap();
+        assert!(matches!(a.add(&c, "bad"), Err(ZyphyrError::InvalidDimension { expected: 2, got: 3 })));
+        assert!(matches!(a.sub(&c, "bad"), Err(ZyphyrError::InvalidDimension { expected: 2, got: 3 })));
+        
+        // Overflow to infinity is rejected like non-finite input
+        let huge = Vector::new("huge", vec![f32::MAX, 1.0]).unwrap();
+        assert!(matches!(huge.add(&huge, "bad"), Err(ZyphyrError::InvalidData(_))));
+        assert!(matches!(huge.sub(&huge.scale(-1.0, "neg"), "bad"), Err(ZyphyrError::InvalidData(_))));
+    }
+
+    #[test]
+    #[should_panic(expected = "non-finite value")]
+    fn test_scale_overflow_panics() {
+        let huge = Vector::new("huge", vec![f32::MAX, 1.0]).unwrap();
+        huge.scale(2.0, "bad");
+    }
+
+    #[test]
+    fn test_new_with_dim() {
+        let v = Vector::new_with_dim("v", vec![1.0, 2.0, 3.0], 3).unwrap();
+        assert_eq!(v.dim(), 3);
+        assert_eq!(v.data(), &[1.0, 2.0, 3.0]);
+        
+        assert!(matches!(
+            Vector::new_with_dim("v", vec![1.0, 2.0], 3),
+            Err(ZyphyrError::InvalidDimension { expected: 3, got: 2 })
+        ));
+        assert!(Vector::new_with_dim("v", vec![], 0).is_err());
+    }
+
+    #[test]
+    fn test_non_finite_values_rejected() {
+        assert!(matches!(Vector::new("nan", vec![1.0, f32::NAN]), Err(ZyphyrError::InvalidData(_))));
+        assert!(matches!(Vector::new("inf", vec![f32::INFINITY, 1.0]), Err(ZyphyrError::InvalidData(_))));
+        assert!(matches!(Vector::from_slice("neg_inf", &[f32::NEG_INFINITY]), Err(ZyphyrError::InvalidData(_))));
+        
+        // The unchecked constructor trusts the caller
+        let unchecked = Vector::new_unchecked("nan", vec![1.0, f32::NAN]).unwrap();
+        assert_eq!(unchecked.dim(), 2);
+        assert!(unchecked.data()[1].is_nan());
+        assert!(Vector::new_unchecked("empty", vec![]).is_err());
+    }
+
+    #[test]
+    fn test_vector_equality_is_id_based() {
+        let a = Vector::new("same", vec![1.0, 2.0]).unwrap();
+        let b = Vector::new("same", vec![9.0, 9.0, 9.0]).unwrap();
+        let c = Vector::new("other", vec![1.0, 2.0]).unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        
+        let mut set = std::collections::HashSet::new();
+        set.insert(a);
+        set.insert(b);
+        assert_eq!(set.len(), 1);
+        set.insert(c);
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_new_normalized() {
+        let mut v = Vector::new_normalized("n", vec![3.0, 4.0, 0.0]).unwrap();
+        assert!(v.is_normalized());
+        assert!((v.norm() - 1.0).abs() < 1e-6);
+        assert_eq!(v.data(), &[0.6, 0.8, 0.0]);
+        assert!(v.raw_data()[v.dim()..].iter().all(|&x| x == 0.0));
+        
+        let mut expected = Vector::new("e", vec![3.0, 4.0, 0.0]).unwrap();
+        assert!(!expected.is_normalized());
+        expected.normalize();
+        assert_eq!(v.data(), expected.data());
+        
+        // Zero vectors stay zero rather than turning into NaN
+        let zero = Vector::new_normalized("z", vec![0.0; 4]).unwrap();
+        assert!(zero.is_normalized());
+        assert_eq!(zero.data(), &[0.0; 4]);
+        
+        assert!(Vector::new_normalized("empty", vec![]).is_err());
+        assert!(matches!(Vector::new_normalized("nan", vec![f32::NAN]), Err(ZyphyrError::InvalidData(_))));
+    }
+
+    #[test]
+    fn test_normalize_denormalize_round_trip() {
+        let original = vec![1.5, -2.0, 6.0, 0.25, 3.0];
+        let mut v = Vector::new("v", original.clone()).unwrap();
+        assert!(!v.is_normalized());
+        
+        // Denormalizing an unnormalized vector changes nothing
+        v.denormalize();
+        assert_eq!(v.data(), original.as_slice());
+        
+        v.normalize();
+        assert!(v.is_normalized());
+        assert!((v.norm() - 1.0).abs() < 1e-6);
+        
+        v.denormalize();
+        assert!(!v.is_normalized());
+        for (restored, expected) in v.data().iter().zip(&original) {
+            assert!((restored - expected).abs() < 1e-5, "{} vs {}", restored, expected);
+        }
+        assert!(v.raw_data()[v.dim()..].iter().all(|&x| x == 0.0));
+        
+        let mut built = Vector::new_normalized("b", vec![3.0, 4.0]).unwrap();
+        built.denormalize();
+        assert_eq!(built.data(), &[3.0, 4.0]);
+        
+        // A zero vector has no magnitude to restore
+        let mut zero = Vector::new("z", vec![0.0; 3]).unwrap();
+        zero.normalize();
+        zero.denormalize();
+        assert!(zero.is_normalized());
+        assert_eq!(zero.data(), &[0.0; 3]);
+    }
+
+    #[test]
+    fn test_f64_vectors() {
+        let a = Vector::<f64>::from_elements("a", vec![1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+        let b = Vector::<f64>::from_elements("b", vec![2.0, 2.0, 3.0, 4.0, 8.0]).unwrap();
+        assert_eq!(a.dim(), 5);
+        assert_eq!(a.data(), &[1.0f64, 2.0, 3.0, 4.0, 5.0]);
+        assert!(a.padded_dim() >= 5 && a.raw_data()[5..].iter().all(|&x| x == 0.0));
+        assert!(a.is_aligned());
+        
+        let distance = DistanceMetric::Euclidean.compute_generic(&a, &b).unwrap();
+        assert!((distance - 10.0f32.sqrt()).abs() < 1e-6);
+        assert!((DistanceMetric::DotProduct.compute_generic(&a, &b).unwrap() - 71.0).abs() < 1e-6);
+        assert!(DistanceMetric::Cosine.compute_generic(&a, &a).unwrap().abs() < 1e-6);
+        
+        // Metrics without an f64 kernel go through f32 slices and still agree
+        let a32 = Vector::new("a", vec![1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+        let b32 = Vector::new("b", vec![2.0, 2.0, 3.0, 4.0, 8.0]).unwrap();
+        let generic = DistanceMetric::Correlation.compute_generic(&a, &b).unwrap();
+        assert!((generic - DistanceMetric::Correlation.compute(&a32, &b32).unwrap()).abs() < 1e-6);
+        assert_eq!(DistanceMetric::Euclidean.compute_generic(&a32, &b32).unwrap(), distance);
+        
+        let short = Vector::<f64>::from_elements("s", vec![1.0]).unwrap();
+        assert!(DistanceMetric::Euclidean.compute_generic(&a, &short).is_err());
+        assert!(Vector::<f64>::from_elements("nan", vec![f64::NAN]).is_err());
+        assert!(Vector::<f64>::from_elements("empty", vec![]).is_err());
+    }
+
+    #[test]
+    fn test_raw_pointer_access() {
+        let v = Vector::new("v", (0..13).map(|i| i as f32).collect()).unwrap();
+        assert_eq!(v.padded_len(), v.padded_dim());
+        
+        let ptr = v.as_ptr();
+        if v.is_aligned() {
+            assert!(is_aligned(ptr as *const u8, 32));
+        }
+        
+        // Safety: the pointer is valid for padded_len elements while `v` is alive and unmodified
+        let through_ptr = unsafe { std::slice::from_raw_parts(ptr, v.padded_len()) };
+        assert_eq!(through_ptr, v.raw_data());
+    }
+
+    #[test]
+    fn test_update_data() {
+        let mut v = Vector::new("v", vec![3.0, 4.0, 0.0]).unwrap();
+        v.normalize();
+        assert_eq!(v.norm(), 1.0);
+        
+        // Apply a delta to one element of the normalized data
+        v.update_data(|data| {
+            assert_eq!(data.len(), 3);
+            data[2] += 2.4;
+        }).unwrap();
+        assert!(!v.is_normalized());
+        assert_eq!(v.cached_norm(), None);
+        assert!(v.raw_data()[v.dim()..].iter().all(|&x| x == 0.0));
+        
+        // [0.6, 0.8, 2.4] has norm 2.6
+        assert!((v.norm() - 2.6).abs() < 1e-6);
+        v.normalize();
+        let expected = [0.6 / 2.6, 0.8 / 2.6, 2.4 / 2.6];
+        for (x, e) in v.data().iter().zip(expected) {
+            assert!((x - e).abs() < 1e-6);
+        }
+        assert!((v.norm() - 1.0).abs() < 1e-6);
+        
+        // Denormalizing returns to the edited data, not the original
+        v.denormalize();
+        assert!((v.data()[2] - 2.4).abs() < 1e-5);
+        
+        // A non-finite edit is rejected and leaves the data untouched
+        let before = v.data().to_vec();
+        assert!(matches!(v.update_data(|data| data[0] = f32::NAN), Err(ZyphyrError::InvalidData(_))));
+        assert!(matches!(v.update_data(|data| data[1] = f32::NEG_INFINITY), Err(ZyphyrError::InvalidData(_))));
+        assert_eq!(v.data(), &before[..]);
+    }
+}
@@ -0,0 +1,5 @@
+pub use self::pca::PcaProjector;
+pub use self::random_projection::RandomProjector;
+
+mod pca;
+mod random_projection;
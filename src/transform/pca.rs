@@ -0,0 +1,163 @@
+use crate::{Vector, VectorCollection, ZyphyrError};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+
+/// Maximum power iterations spent on each principal component
+const MAX_ITERATIONS: usize = 1000;
+
+/// Convergence threshold on the change of a component between iterations
+const TOLERANCE: f64 = 1e-10;
+
+/// Linear projection onto the top principal components of a fitted collection
+#[derive(Debug, Clone)]
+pub struct PcaProjector {
+    mean: Vec<f32>,
+    components: Vec<Vec<f32>>,  // Unit-length principal axes, largest variance first
+}
+
+impl PcaProjector {
+    /// Project `v` onto the principal components, returning a vector with the same id.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` does not have the dimension the projector was fitted on.
+    pub fn transform(&self, v: &Vector) -> Vector {
+        assert_eq!(v.dim(), self.input_dim(), "vector dimension does not match the fitted PCA");
+        
+        let projected: Vec<f32> = self.components
+            .iter()
+            .map(|axis| {
+                axis.iter()
+                    .zip(v.data().iter().zip(&self.mean))
+                    .map(|(a, (x, m))| a * (x - m))
+                    .sum()
+            })
+            .collect();
+        Vector::new(v.id(), projected).expect("PCA output dimension is non-zero")
+    }
+
+    /// Dimension of the vectors accepted by [`PcaProjector::transform`]
+    pub fn input_dim(&self) -> usize {
+        self.mean.len()
+    }
+
+    /// Dimension of the vectors produced by [`PcaProjector::transform`]
+    pub fn output_dim(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Principal axes, ordered by decreasing explained variance
+    pub fn components(&self) -> &[Vec<f32>] {
+        &self.components
+    }
+}
+
+impl VectorCollection {
+    /// Fit a PCA projection onto the `target_dim` directions of largest variance.
+    /// The data is centered on its mean before the covariance eigenvectors are
+    /// found by power iteration.
+    pub fn fit_pca(&self, target_dim: usize) -> Result<PcaProjector, ZyphyrError> {
+        let dim = match self.dimensions() {
+            Some(dim) if !self.is_empty() => dim,
+            _ => return Err(ZyphyrError::Other("Cannot fit PCA on an empty collection".to_string())),
+        };
+        if target_dim == 0 || target_dim > dim {
+            return Err(ZyphyrError::Other(format!(
+                "PCA target dimension must be in 1..={}, got {}",
+                dim, target_dim
+            )));
+        }
+        
+        // Accumulate in f64 so large collections don't lose precision
+        let count = self.len() as f64;
+        let mut mean = vec![0.0f64; dim];
+        for v in self.iter() {
+            for (m, &x) in mean.iter_mut().zip(v.data()) {
+                *m += x as f64;
+            }
+        }
+        mean.iter_mut().for_each(|m| *m /= count);
+        
+        let mut covariance = vec![0.0f64; dim * dim];
+        let mut centered = vec![0.0f64; dim];
+        for v in self.iter() {
+            for ((c, &x), m) in centered.iter_mut().zip(v.data()).zip(&mean) {
+                *c = x as f64 - m;
+            }
+            for i in 0..dim {
+                let row = &mut covariance[i * dim..(i + 1) * dim];
+                for (cell, c) in row.iter_mut().zip(&centered) {
+                    *cell += centered[i] * c;
+                }
+            }
+        }
+        covariance.iter_mut().for_each(|c| *c /= count);
+        
+        // Fixed seed keeps the fitted axes reproducible
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut components: Vec<Vec<f64>> = Vec::with_capacity(target_dim);
+        for _ in 0..target_dim {
+            let start: Vec<f64> = (0..dim).map(|_| rng.random_range(-1.0..1.0)).collect();
+            let axis = power_iteration(&covariance, dim, start, &components);
+            components.push(axis);
+        }
+        
+        Ok(PcaProjector {
+            mean: mean.into_iter().map(|m| m as f32).collect(),
+            components: components
+                .into_iter()
+                .map(|axis| axis.into_iter().map(|a| a as f32).collect())
+                .collect(),
+        })
+    }
+}
+
+// Find the dominant eigenvector of `matrix` orthogonal to all of `found`.
+// If the remaining variance is zero, the orthogonalized start vector is returned.
+fn power_iteration(matrix: &[f64], dim: usize, start: Vec<f64>, found: &[Vec<f64>]) -> Vec<f64> {
+    let mut current = start;
+    orthogonalize(&mut current, found);
+    if !normalize(&mut current) {
+        // The random start was degenerate; fall back to a basis vector
+        current = (0..dim).map(|i| if i == found.len() { 1.0 } else { 0.0 }).collect();
+        orthogonalize(&mut current, found);
+        normalize(&mut current);
+    }
+    
+    for _ in 0..MAX_ITERATIONS {
+        let mut next: Vec<f64> = matrix
+            .chunks(dim)
+            .map(|row| row.iter().zip(&current).map(|(a, b)| a * b).sum())
+            .collect();
+        orthogonalize(&mut next, found);
+        if !normalize(&mut next) {
+            return current;
+        }
+        
+        let change: f64 = next.iter().zip(&current).map(|(a, b)| (a - b).abs()).sum();
+        current = next;
+        if change < TOLERANCE {
+            break;
+        }
+    }
+    current
+}
+
+fn orthogonalize(v: &mut [f64], basis: &[Vec<f64>]) {
+    for axis in basis {
+        let projection: f64 = v.iter().zip(axis).map(|(a, b)| a * b).sum();
+        for (x, a) in v.iter_mut().zip(axis) {
+            *x -= projection * a;
+        }
+    }
+}
+
+// Scale `v` to unit length, returning false if it is (numerically) zero
+fn normalize(v: &mut [f64]) -> bool {
+    let norm = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+    if norm < 1e-12 {
+        return false;
+    }
+    v.iter_mut().for_each(|x| *x /= norm);
+    true
+}
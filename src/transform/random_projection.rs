@@ -0,0 +1,63 @@
+use crate::{Vector, ZyphyrError};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+
+/// Gaussian random projection: a fixed `output_dim × input_dim` matrix of
+/// N(0, 1) entries scaled by `1/sqrt(output_dim)`. By Johnson–Lindenstrauss,
+/// pairwise distances are approximately preserved.
+#[derive(Debug, Clone)]
+pub struct RandomProjector {
+    input_dim: usize,
+    matrix: Vec<f32>,  // Row-major, one row per output dimension
+}
+
+impl RandomProjector {
+    /// Draw a projection matrix; the same `seed` always yields the same matrix
+    pub fn new(input_dim: usize, output_dim: usize, seed: u64) -> Result<Self, ZyphyrError> {
+        if input_dim == 0 || output_dim == 0 {
+            return Err(ZyphyrError::Other(
+                "Random projection dimensions must be non-zero".to_string(),
+            ));
+        }
+        
+        let mut rng = StdRng::seed_from_u64(seed);
+        let scale = 1.0 / (output_dim as f32).sqrt();
+        let matrix = (0..input_dim * output_dim)
+            .map(|_| standard_normal(&mut rng) * scale)
+            .collect();
+        
+        Ok(RandomProjector { input_dim, matrix })
+    }
+
+    /// Project `v`, returning a vector with the same id.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` does not have the projector's input dimension.
+    pub fn transform(&self, v: &Vector) -> Vector {
+        assert_eq!(v.dim(), self.input_dim, "vector dimension does not match the projector");
+        
+        let projected: Vec<f32> = self.matrix
+            .chunks(self.input_dim)
+            .map(|row| row.iter().zip(v.data()).map(|(a, x)| a * x).sum())
+            .collect();
+        Vector::new(v.id(), projected).expect("projection output dimension is non-zero")
+    }
+
+    /// Dimension of the vectors accepted by [`RandomProjector::transform`]
+    pub fn input_dim(&self) -> usize {
+        self.input_dim
+    }
+
+    /// Dimension of the vectors produced by [`RandomProjector::transform`]
+    pub fn output_dim(&self) -> usize {
+        self.matrix.len() / self.input_dim
+    }
+}
+
+// Box–Muller transform, so we don't need a distributions crate for one sampler
+fn standard_normal(rng: &mut StdRng) -> f32 {
+    let u1: f64 = 1.0 - rng.random::<f64>();  // (0, 1], keeps ln finite
+    let u2: f64 = rng.random();
+    ((-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()) as f32
+}
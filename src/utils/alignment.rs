@@ -6,10 +6,11 @@ pub const SIMD_ALIGNMENT: usize = 32;
 
 /// Check if a pointer is properly aligned for SIMD operations
 pub fn is_aligned(ptr: *const u8, align: usize) -> bool {
-    (ptr as usize) % align == 0
+    (ptr as usize).is_multiple_of(align)
 }
 
 /// Allocate memory with specific alignment for SIMD operations
+#[allow(dead_code)] // `Vector` allocates through `AlignedVec`; kept for raw buffers
 pub unsafe fn aligned_alloc<T>(len: usize) -> (*mut T, Layout) {
     let size = mem::size_of::<T>() * len;
     let layout = Layout::from_size_align(size, SIMD_ALIGNMENT)
@@ -21,6 +22,7 @@ pub unsafe fn aligned_alloc<T>(len: usize) -> (*mut T, Layout) {
 }
 
 /// Deallocate memory that was allocated with aligned_alloc
+#[allow(dead_code)]
 pub unsafe fn aligned_dealloc<T>(ptr: *mut T, layout: Layout) {
     unsafe {
         dealloc(ptr as *mut u8, layout);
@@ -29,10 +31,18 @@ pub unsafe fn aligned_dealloc<T>(ptr: *mut T, layout: Layout) {
 
 /// Pad a dimension to the nearest multiple of SIMD width
 pub fn pad_dimension(dim: usize, simd_width: usize) -> usize {
-    ((dim + simd_width - 1) / simd_width) * simd_width
+    dim.div_ceil(simd_width) * simd_width
 }
 
-/// Get the optimal SIMD width for the current platform
+/// Get the optimal SIMD width for the current platform, as the number of f32
+/// lanes in the widest vector register detected at runtime: 16 for AVX-512,
+/// 8 for AVX2, 4 for SSE or NEON, and 1 when no SIMD support is available.
+///
+/// ```
+/// let width = zyphyr::get_simd_width();
+/// assert!(width.is_power_of_two());
+/// assert!(width <= 16);
+/// ```
 pub fn get_simd_width() -> usize {
     #[cfg(target_arch = "x86_64")]
     {
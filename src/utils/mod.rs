@@ -1,3 +1,2 @@
 pub mod alignment;
-
-pub use alignment::{SIMD_ALIGNMENT, is_aligned, pad_dimension, get_simd_width};
\ No newline at end of file
+pub mod simd;
@@ -0,0 +1,78 @@
+use std::fmt;
+use std::mem;
+
+/// SIMD instruction sets available on the running CPU
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SimdCapabilities {
+    pub sse: bool,
+    pub avx: bool,
+    pub avx2: bool,
+    pub avx512: bool,
+    pub neon: bool,
+}
+
+/// Detect SIMD support at runtime
+pub fn detect_simd() -> SimdCapabilities {
+    #[allow(unused_mut)]
+    let mut caps = SimdCapabilities::default();
+    
+    #[cfg(target_arch = "x86_64")]
+    {
+        caps.sse = std::arch::is_x86_feature_detected!("sse");
+        caps.avx = std::arch::is_x86_feature_detected!("avx");
+        caps.avx2 = std::arch::is_x86_feature_detected!("avx2");
+        caps.avx512 = std::arch::is_x86_feature_detected!("avx512f");
+    }
+    
+    #[cfg(target_arch = "aarch64")]
+    {
+        caps.neon = std::arch::is_aarch64_feature_detected!("neon");
+    }
+    
+    caps
+}
+
+/// Hint the CPU to pull `data` into cache ahead of a read. Compiles to nothing
+/// on targets without a stable prefetch instruction.
+#[inline(always)]
+pub(crate) fn prefetch_read<T>(data: &[T]) {
+    #[cfg(target_arch = "x86_64")]
+    {
+        use std::arch::x86_64::{_MM_HINT_T0, _mm_prefetch};
+        const CACHE_LINE: usize = 64;
+        
+        let ptr = data.as_ptr() as *const i8;
+        for offset in (0..mem::size_of_val(data)).step_by(CACHE_LINE) {
+            // SSE is baseline on x86_64, and prefetching never faults
+            unsafe { _mm_prefetch::<_MM_HINT_T0>(ptr.wrapping_add(offset)) };
+        }
+    }
+    
+    #[cfg(not(target_arch = "x86_64"))]
+    let _ = data;
+}
+
+impl fmt::Display for SimdCapabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // SSE is baseline on x86_64 and was never part of this report, so it stays
+        // out to keep the output format stable
+        let features = [
+            (self.avx, "AVX"),
+            (self.avx2, "AVX2"),
+            (self.avx512, "AVX-512"),
+            (self.neon, "NEON"),
+        ];
+        
+        let mut any = false;
+        for (supported, name) in features {
+            if supported {
+                writeln!(f, "{} supported", name)?;
+                any = true;
+            }
+        }
+        if !any {
+            write!(f, "No advanced SIMD features detected")?;
+        }
+        Ok(())
+    }
+}
@@ -1,11 +1,211 @@
-use crate::{Vector, ZyphyrError, DistanceMetric};
-use std::collections::HashMap;
+use crate::{Vector, ZyphyrError, DistanceMetric, Metadata};
+use crate::vector::distance::{Distance, DotProduct, l2_norm};
+use crate::vector::vector_aligned::check_finite;
+use crate::utils::simd::prefetch_read;
+use crate::utils::alignment::{get_simd_width, pad_dimension};
+use std::borrow::Cow;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
 use std::mem;
+use std::path::Path;
+
+/// Magic bytes identifying a serialized collection file
+const FILE_MAGIC: &[u8; 4] = b"ZYPH";
+
+/// Magic bytes and layout version prefixed to `to_bincode` payloads
+#[cfg(feature = "bincode")]
+const BINCODE_MAGIC: &[u8; 4] = b"ZYBC";
+#[cfg(feature = "bincode")]
+const BINCODE_VERSION: u8 = 1;
+
+/// Decoded form of one `to_bincode` record: id, data, metadata
+#[cfg(feature = "bincode")]
+type BincodeRecord = (String, Vec<f32>, Option<Metadata>);
+
+/// JSON representation of a single vector: `{"id": "...", "data": [...]}`
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct VectorRecord {
+    id: String,
+    data: Vec<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    metadata: Option<Metadata>,
+}
 
 pub struct VectorCollection {
     vectors: Vec<Vector>,
     id_to_index: HashMap<String, usize>,
+    deleted: Vec<bool>,         // Tombstones set by soft_remove, parallel to `vectors`
+    deleted_count: usize,       // Number of `true` entries in `deleted`
     dimensions: Option<usize>,  // Track consistent dimensions if applicable
+    pinned_dimension: Option<usize>,  // Dimension fixed by the builder, kept across `clear`
+    default_metric: DistanceMetric,
+    normalize_on_insert: bool,  // Store unit vectors so cosine search is a dot product
+    memory_bytes: usize,        // Sum of `memory_usage()` over stored vectors, tombstones included
+    max_memory_bytes: Option<usize>,  // Budget for `memory_bytes`, enforced on insert
+}
+
+/// How search results with equal distances are ordered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TieBreak {
+    /// Lexicographic id order
+    #[default]
+    ById,
+    /// Storage order, which is insertion order unless a swap `remove` moved entries
+    ByInsertionOrder,
+}
+
+/// Tuning knobs for [`VectorCollection::search_with_options`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchOptions {
+    pub tie_break: TieBreak,
+    /// Prefetch the next vector's data while scoring the current one (on by default)
+    pub prefetch: bool,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        SearchOptions { tie_break: TieBreak::default(), prefetch: true }
+    }
+}
+
+/// Reusable buffers for [`VectorCollection::search_into`]. Keep one per thread
+/// and pass it to every query to avoid per-search allocations.
+#[derive(Debug, Clone, Default)]
+pub struct SearchScratch {
+    candidates: Vec<(f32, usize)>,  // (distance, storage position) for every scanned vector
+    results: Vec<(String, f32)>,
+}
+
+impl SearchScratch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Summary statistics from [`VectorCollection::stats`]. Every field except
+/// `count` is `None` for an empty collection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollectionStats {
+    pub count: usize,
+    pub mean: Option<Vec<f32>>,
+    pub min: Option<Vec<f32>>,  // Per-dimension minimum
+    pub max: Option<Vec<f32>>,  // Per-dimension maximum
+    pub average_norm: Option<f32>,
+}
+
+/// Builder for configuring a [`VectorCollection`] up front
+#[derive(Debug, Clone)]
+pub struct VectorCollectionBuilder {
+    dimension: Option<usize>,
+    capacity: usize,
+    default_metric: DistanceMetric,
+    normalize_on_insert: bool,
+    max_memory_bytes: Option<usize>,
+}
+
+impl Default for VectorCollectionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VectorCollectionBuilder {
+    pub fn new() -> Self {
+        VectorCollectionBuilder {
+            dimension: None,
+            capacity: 0,
+            default_metric: DistanceMetric::Euclidean,
+            normalize_on_insert: false,
+            max_memory_bytes: None,
+        }
+    }
+
+    /// Pin the vector dimension so the first insert is validated too,
+    /// instead of the dimension being inferred from it
+    pub fn dimension(mut self, dimension: usize) -> Self {
+        self.dimension = Some(dimension);
+        self
+    }
+
+    /// Pre-allocate room for `capacity` vectors
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Metric used by [`VectorCollection::search_default`]
+    pub fn default_metric(mut self, metric: DistanceMetric) -> Self {
+        self.default_metric = metric;
+        self
+    }
+
+    /// Normalize every vector to unit length as it is inserted, letting cosine
+    /// search use a plain dot product. Stored vectors then return their
+    /// normalized values from [`Vector::data`], not the originals.
+    pub fn normalize_on_insert(mut self, normalize: bool) -> Self {
+        self.normalize_on_insert = normalize;
+        self
+    }
+
+    /// Reject inserts once the stored vectors' combined [`Vector::memory_usage`]
+    /// would exceed `limit` bytes. Container overhead (spare capacity, the id
+    /// index) isn't counted against the budget.
+    pub fn max_memory_bytes(mut self, limit: usize) -> Self {
+        self.max_memory_bytes = Some(limit);
+        self
+    }
+
+    pub fn build(self) -> VectorCollection {
+        let mut collection = VectorCollection::with_capacity(self.capacity);
+        collection.dimensions = self.dimension;
+        collection.pinned_dimension = self.dimension;
+        collection.default_metric = self.default_metric;
+        collection.normalize_on_insert = self.normalize_on_insert;
+        collection.max_memory_bytes = self.max_memory_bytes;
+        collection
+    }
+}
+
+impl Default for VectorCollection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FromIterator<Vector> for VectorCollection {
+    /// Collect vectors into a new collection.
+    ///
+    /// # Panics
+    ///
+    /// Panics on a duplicate id or dimension mismatch; use
+    /// [`VectorCollection::try_from_iter`] to handle those as errors.
+    fn from_iter<I: IntoIterator<Item = Vector>>(iter: I) -> Self {
+        match Self::try_from_iter(iter) {
+            Ok(collection) => collection,
+            Err(e) => panic!("cannot collect vectors into a VectorCollection: {}", e),
+        }
+    }
+}
+
+impl Extend<Vector> for VectorCollection {
+    /// Insert every vector from the iterator, reserving room from its size hint.
+    ///
+    /// # Panics
+    ///
+    /// Panics on a duplicate id or dimension mismatch, like `FromIterator`.
+    /// Vectors before the offending one have already been inserted.
+    fn extend<I: IntoIterator<Item = Vector>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for vector in iter {
+            if let Err(e) = self.insert(vector) {
+                panic!("cannot extend VectorCollection: {}", e);
+            }
+        }
+    }
 }
 
 impl VectorCollection {
@@ -13,48 +213,140 @@ impl VectorCollection {
         VectorCollection {
             vectors: Vec::new(),
             id_to_index: HashMap::new(),
+            deleted: Vec::new(),
+            deleted_count: 0,
             dimensions: None,
+            pinned_dimension: None,
+            default_metric: DistanceMetric::Euclidean,
+            normalize_on_insert: false,
+            memory_bytes: 0,
+            max_memory_bytes: None,
         }
     }
 
+    /// Build a collection from an iterator, stopping at the first vector `insert` rejects
+    pub fn try_from_iter<I: IntoIterator<Item = Vector>>(iter: I) -> Result<Self, ZyphyrError> {
+        let iter = iter.into_iter();
+        let mut collection = VectorCollection::with_capacity(iter.size_hint().0);
+        for vector in iter {
+            collection.insert(vector)?;
+        }
+        Ok(collection)
+    }
+
+    pub fn builder() -> VectorCollectionBuilder {
+        VectorCollectionBuilder::new()
+    }
+
     pub fn with_capacity(capacity: usize) -> Self {
         VectorCollection {
             vectors: Vec::with_capacity(capacity),
             id_to_index: HashMap::with_capacity(capacity),
+            deleted: Vec::with_capacity(capacity),
+            deleted_count: 0,
             dimensions: None,
+            pinned_dimension: None,
+            default_metric: DistanceMetric::Euclidean,
+            normalize_on_insert: false,
+            memory_bytes: 0,
+            max_memory_bytes: None,
         }
     }
 
-    pub fn insert(&mut self, vector: Vector) -> Result<(), ZyphyrError> {
+    pub fn insert(&mut self, mut vector: Vector) -> Result<(), ZyphyrError> {
         // Check for consistent dimensions
-        if let Some(dims) = self.dimensions {
-            if vector.dim() != dims {
-                return Err(ZyphyrError::InvalidDimension { 
-                    expected: dims, 
-                    got: vector.dim() 
-                });
-            }
-        } else if !self.is_empty() {
-            self.dimensions = Some(vector.dim());
-        } else {
-            self.dimensions = Some(vector.dim());
+        if let Some(dims) = self.dimensions
+            && vector.dim() != dims
+        {
+            return Err(ZyphyrError::InvalidDimension { 
+                expected: dims, 
+                got: vector.dim() 
+            });
         }
 
         if self.id_to_index.contains_key(vector.id()) {
             return Err(ZyphyrError::Other(format!("Duplicate ID: {}", vector.id())));
         }
         
+        let bytes = vector.memory_usage();
+        self.check_memory_budget(self.memory_bytes + bytes)?;
+        
+        // Only adopt the first vector's dimension once the insert can't fail
+        self.dimensions.get_or_insert(vector.dim());
+        
+        if self.normalize_on_insert {
+            vector.normalize();
+        }
+        
+        // Cache the norm up front so cosine search doesn't recompute it per query
+        vector.norm();
+        
         let index = self.vectors.len();
         self.id_to_index.insert(vector.id().to_string(), index);
         self.vectors.push(vector);
+        self.deleted.push(false);
+        self.memory_bytes += bytes;
         Ok(())
     }
 
+    fn check_memory_budget(&self, needed: usize) -> Result<(), ZyphyrError> {
+        match self.max_memory_bytes {
+            Some(limit) if needed > limit => {
+                Err(ZyphyrError::MemoryLimitExceeded { used: self.memory_bytes, limit })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // Queries must match the stored dimension; an empty, unpinned collection accepts any
+    fn check_query_dimension(&self, query: &Vector) -> Result<(), ZyphyrError> {
+        match self.dimensions {
+            Some(dims) if query.dim() != dims => {
+                Err(ZyphyrError::InvalidDimension { expected: dims, got: query.dim() })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Replace the vector with the same id in place, or insert it if absent.
+    /// Returns `Ok(true)` for an insert and `Ok(false)` for an update.
+    pub fn upsert(&mut self, mut vector: Vector) -> Result<bool, ZyphyrError> {
+        let index = match self.id_to_index.get(vector.id()) {
+            Some(&index) => index,
+            None => {
+                self.insert(vector)?;
+                return Ok(true);
+            }
+        };
+        
+        if let Some(dims) = self.dimensions
+            && vector.dim() != dims
+        {
+            return Err(ZyphyrError::InvalidDimension { 
+                expected: dims, 
+                got: vector.dim() 
+            });
+        }
+        
+        let bytes = vector.memory_usage();
+        let replaced = self.vectors[index].memory_usage();
+        self.check_memory_budget(self.memory_bytes.saturating_sub(replaced) + bytes)?;
+        
+        if self.normalize_on_insert {
+            vector.normalize();
+        }
+        vector.norm();
+        self.vectors[index] = vector;
+        self.memory_bytes = self.memory_bytes.saturating_sub(replaced) + bytes;
+        Ok(false)
+    }
+
     // Add batch insertion for efficiency
     pub fn batch_insert(&mut self, vectors: Vec<Vector>) -> Result<(), ZyphyrError> {
         // Pre-allocate capacity
         self.vectors.reserve(vectors.len());
         self.id_to_index.reserve(vectors.len());
+        self.deleted.reserve(vectors.len());
         
         for vector in vectors {
             self.insert(vector)?;
@@ -62,32 +354,155 @@ impl VectorCollection {
         Ok(())
     }
 
-    // Add chunk-based iteration for parallel processing
+    /// Insert vectors as an iterator yields them, without collecting the whole
+    /// batch first. Returns how many were inserted. Stops at the first error,
+    /// whether yielded by the iterator or raised by the insert. Vectors before
+    /// the failing item stay inserted, but the error doesn't carry their count;
+    /// take it from the change in [`VectorCollection::len`] across the call.
+    pub fn insert_stream<I>(&mut self, iter: I) -> Result<usize, ZyphyrError>
+    where
+        I: Iterator<Item = Result<Vector, ZyphyrError>>,
+    {
+        let mut inserted = 0;
+        for vector in iter {
+            self.insert(vector?)?;
+            inserted += 1;
+        }
+        Ok(inserted)
+    }
+
+    /// Iterate over all vectors in storage order (insertion order until a remove swaps entries)
+    pub fn iter(&self) -> impl Iterator<Item = &Vector> {
+        self.live_entries().map(|(_, v)| v)
+    }
+
+    // Live vectors paired with their storage position, skipping soft-removed slots
+    fn live_entries(&self) -> impl Iterator<Item = (usize, &Vector)> {
+        self.vectors
+            .iter()
+            .enumerate()
+            .zip(self.deleted.iter())
+            .filter(|(_, deleted)| !**deleted)
+            .map(|(entry, _)| entry)
+    }
+
+    /// Iterate over all vector ids in the same order as [`VectorCollection::iter`]
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.iter().map(|v| v.id())
+    }
+
+    // Add chunk-based iteration for parallel processing.
+    // Chunks hold live vectors only and never span a soft-removed slot, so after
+    // soft removes some chunks can be shorter than `chunk_size` until `compact`.
     pub fn chunks(&self, chunk_size: usize) -> impl Iterator<Item = &[Vector]> {
-        self.vectors.chunks(chunk_size)
+        let mut start = 0;
+        self.deleted.split(|&deleted| deleted).flat_map(move |run| {
+            let live = &self.vectors[start..start + run.len()];
+            start += run.len() + 1;
+            live.chunks(chunk_size)
+        })
     }
 
-    // Add memory usage reporting
+    /// Copy every live vector's padded data into one flat row-major buffer, e.g.
+    /// for GPU upload. Returns the buffer and its stride (the padded dimension);
+    /// row `i` is `buffer[i * stride..(i + 1) * stride]`, in [`VectorCollection::iter`] order.
+    pub fn to_contiguous(&self) -> (Vec<f32>, usize) {
+        // Equal dimensions always pad to the same length
+        let stride = self.iter().next().map_or(0, |v| v.padded_dim());
+        let mut buffer = Vec::with_capacity(self.len() * stride);
+        for v in self.iter() {
+            buffer.extend_from_slice(v.raw_data());
+        }
+        (buffer, stride)
+    }
+
+    // Add memory usage reporting, including spare capacity in the backing storage
     pub fn memory_usage(&self) -> usize {
-        let vectors_memory: usize = self.vectors.iter()
-            .map(|v| v.memory_usage())
-            .sum();
-            
-        let hashmap_memory = self.id_to_index.len() * 
+        // Per-vector usage is kept in a running counter, so this is O(1)
+        self.container_overhead() + self.memory_bytes
+    }
+
+    // Full O(n) recomputation, to check the running counter against
+    #[cfg(test)]
+    pub(crate) fn recompute_memory_usage(&self) -> usize {
+        self.container_overhead() + self.vectors.iter().map(|v| v.memory_usage()).sum::<usize>()
+    }
+
+    // Memory not attributable to any single vector: spare slots, the id index,
+    // tombstone flags and the collection itself
+    fn container_overhead(&self) -> usize {
+        let spare_slots = self.vectors.capacity() - self.vectors.len();
+        let hashmap_memory = self.id_to_index.capacity() * 
             (mem::size_of::<String>() + mem::size_of::<usize>());
+        let tombstone_memory = self.deleted.capacity() * mem::size_of::<bool>();
             
-        vectors_memory + hashmap_memory + mem::size_of::<Self>()
+        spare_slots * mem::size_of::<Vector>() + hashmap_memory + tombstone_memory + mem::size_of::<Self>()
+    }
+
+    /// Number of vectors the collection can hold without reallocating
+    pub fn capacity(&self) -> usize {
+        self.vectors.capacity()
+    }
+
+    /// Reserve room for at least `additional` more vectors
+    pub fn reserve(&mut self, additional: usize) {
+        self.vectors.reserve(additional);
+        self.id_to_index.reserve(additional);
+        self.deleted.reserve(additional);
+    }
+
+    /// Release spare capacity, e.g. once ingestion has finished or after many removals
+    pub fn shrink_to_fit(&mut self) {
+        self.vectors.shrink_to_fit();
+        self.id_to_index.shrink_to_fit();
+        self.deleted.shrink_to_fit();
     }
 
     pub fn get(&self, id: &str) -> Option<&Vector> {
         self.id_to_index.get(id).map(|&index| &self.vectors[index])
     }
 
-    pub fn get_mut(&mut self, id: &str) -> Option<&mut Vector> {
+    // Unchecked mutable access, so tests can corrupt the collection on purpose
+    #[cfg(test)]
+    pub(crate) fn get_mut(&mut self, id: &str) -> Option<&mut Vector> {
         let index = *self.id_to_index.get(id)?;
         Some(&mut self.vectors[index])
     }
 
+    /// Edit a stored vector's values in place through [`Vector::update_data`].
+    /// On a `normalize_on_insert` collection `f` sees the stored unit vector,
+    /// and the result is normalized again afterwards.
+    ///
+    /// Fails with `ZyphyrError::IdNotFound` if `id` is missing, or
+    /// `ZyphyrError::InvalidData` if the edit produces a non-finite value.
+    pub fn update_data(&mut self, id: &str, f: impl FnOnce(&mut [f32])) -> Result<(), ZyphyrError> {
+        let index = *self.id_to_index.get(id).ok_or_else(|| ZyphyrError::IdNotFound(id.to_string()))?;
+        let vector = &mut self.vectors[index];
+        vector.update_data(f)?;
+        
+        if self.normalize_on_insert {
+            vector.normalize();
+        }
+        vector.norm();
+        Ok(())
+    }
+
+    /// Like [`VectorCollection::get`], but reports a missing id as `ZyphyrError::IdNotFound`
+    pub fn try_get(&self, id: &str) -> Result<&Vector, ZyphyrError> {
+        self.get(id).ok_or_else(|| ZyphyrError::IdNotFound(id.to_string()))
+    }
+
+    /// Look up several ids at once, returning one entry per requested id in order
+    pub fn get_many(&self, ids: &[&str]) -> Vec<Option<&Vector>> {
+        ids.iter().map(|id| self.get(id)).collect()
+    }
+
+    /// Like [`VectorCollection::get_many`], but fails with `ZyphyrError::IdNotFound`
+    /// on the first missing id
+    pub fn try_get_many(&self, ids: &[&str]) -> Result<Vec<&Vector>, ZyphyrError> {
+        ids.iter().map(|id| self.try_get(id)).collect()
+    }
+
     pub fn contains(&self, id: &str) -> bool {
         self.id_to_index.contains_key(id)
     }
@@ -104,39 +519,1218 @@ impl VectorCollection {
             // If not the last element, swap with last and update index
             let last_index = self.vectors.len() - 1;
             self.vectors.swap(index, last_index);
+            self.deleted.swap(index, last_index);
             
-            // Update the mapping for the swapped element
-            let swapped_id = self.vectors[index].id().to_string();
-            self.id_to_index.insert(swapped_id, index);
+            // Update the mapping for the swapped element, unless it's a tombstone
+            if !self.deleted[index] {
+                let swapped_id = self.vectors[index].id().to_string();
+                self.id_to_index.insert(swapped_id, index);
+            }
         }
         
         // Remove and return
-        Some(self.vectors.pop()?)
+        self.deleted.pop();
+        let removed = self.vectors.pop()?;
+        self.memory_bytes = self.memory_bytes.saturating_sub(removed.memory_usage());
+        Some(removed)
     }
 
+    /// Remove a vector while preserving the storage order of the rest.
+    /// This shifts every later vector down and reindexes it, so it costs O(n)
+    /// compared to the O(1) swap in [`VectorCollection::remove`].
+    pub fn remove_stable(&mut self, id: &str) -> Option<Vector> {
+        let index = self.id_to_index.remove(id)?;
+        let removed = self.vectors.remove(index);
+        self.deleted.remove(index);
+        self.memory_bytes = self.memory_bytes.saturating_sub(removed.memory_usage());
+        
+        for (offset, vector) in self.vectors[index..].iter().enumerate() {
+            if self.deleted[index + offset] {
+                continue;
+            }
+            if let Some(slot) = self.id_to_index.get_mut(vector.id()) {
+                *slot = index + offset;
+            }
+        }
+        Some(removed)
+    }
+
+    /// Mark a vector as deleted without moving any other vector. The slot stays
+    /// allocated and is skipped by lookups and searches until [`VectorCollection::compact`]
+    /// reclaims it. Returns `false` if the id was not present.
+    pub fn soft_remove(&mut self, id: &str) -> bool {
+        match self.id_to_index.remove(id) {
+            Some(index) => {
+                self.deleted[index] = true;
+                self.deleted_count += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Physically drop every soft-removed slot in one pass, preserving the order of the rest
+    pub fn compact(&mut self) {
+        if self.deleted_count == 0 {
+            return;
+        }
+        
+        let mut deleted = self.deleted.iter();
+        self.vectors.retain(|_| !deleted.next().copied().unwrap_or(false));
+        self.rebuild_id_index();
+    }
+
+    /// Rebuild the id lookup map from scratch by scanning the stored vectors, so
+    /// no stale entries survive churn or an inconsistent load. Soft-removed slots
+    /// stay in place and are left out of the map.
+    pub fn reindex(&mut self) {
+        let mut id_to_index = HashMap::with_capacity(self.vectors.len() - self.deleted_count);
+        self.memory_bytes = 0;
+        for (index, (vector, deleted)) in self.vectors.iter().zip(&self.deleted).enumerate() {
+            self.memory_bytes += vector.memory_usage();
+            if !deleted {
+                id_to_index.insert(vector.id().to_string(), index);
+            }
+        }
+        self.id_to_index = id_to_index;
+    }
+
+    /// Check internal consistency: every id map entry points at a live vector
+    /// with that id, every live vector is reachable through the map, ids are
+    /// unique, and all stored vectors share the collection's dimension. Reports
+    /// the first violation as `ZyphyrError::Corrupt`.
+    pub fn validate(&self) -> Result<(), ZyphyrError> {
+        let corrupt = |message: String| Err(ZyphyrError::Corrupt(message));
+        
+        if self.deleted.len() != self.vectors.len() {
+            return corrupt(format!(
+                "{} tombstone flags for {} vectors",
+                self.deleted.len(),
+                self.vectors.len()
+            ));
+        }
+        let tombstones = self.deleted.iter().filter(|&&d| d).count();
+        if tombstones != self.deleted_count {
+            return corrupt(format!("deleted count is {} but {} slots are tombstoned", self.deleted_count, tombstones));
+        }
+        
+        for (id, &index) in &self.id_to_index {
+            match self.vectors.get(index) {
+                None => return corrupt(format!("id {} maps to slot {}, past the end", id, index)),
+                Some(_) if self.deleted[index] => {
+                    return corrupt(format!("id {} maps to soft-removed slot {}", id, index));
+                }
+                Some(v) if v.id() != id => {
+                    return corrupt(format!("id {} maps to slot {} holding {}", id, index, v.id()));
+                }
+                Some(_) => {}
+            }
+        }
+        
+        let mut seen = HashSet::with_capacity(self.len());
+        for (index, v) in self.live_entries() {
+            if !seen.insert(v.id()) {
+                return corrupt(format!("duplicate id {}", v.id()));
+            }
+            if self.id_to_index.get(v.id()) != Some(&index) {
+                return corrupt(format!("vector {} in slot {} is missing from the id map", v.id(), index));
+            }
+        }
+        
+        if let Some(dims) = self.dimensions
+            && let Some(v) = self.vectors.iter().find(|v| v.dim() != dims)
+        {
+            return corrupt(format!("vector {} has dimension {}, expected {}", v.id(), v.dim(), dims));
+        }
+        Ok(())
+    }
+
+    /// Keep only the vectors for which `f` returns true. Soft-removed slots are
+    /// dropped too, so the collection is compact afterwards.
+    pub fn retain(&mut self, mut f: impl FnMut(&Vector) -> bool) {
+        let mut deleted = self.deleted.iter();
+        self.vectors.retain(|v| !deleted.next().copied().unwrap_or(false) && f(v));
+        self.rebuild_id_index();
+        
+        if self.vectors.is_empty() {
+            self.dimensions = self.pinned_dimension;
+        }
+    }
+
+    // Re-map every id to its current slot, assuming no tombstones remain
+    fn rebuild_id_index(&mut self) {
+        self.id_to_index.clear();
+        self.memory_bytes = 0;
+        for (index, vector) in self.vectors.iter().enumerate() {
+            self.id_to_index.insert(vector.id().to_string(), index);
+            self.memory_bytes += vector.memory_usage();
+        }
+        self.deleted = vec![false; self.vectors.len()];
+        self.deleted_count = 0;
+    }
+
+    /// Move every vector from `other` into this collection. Fails without
+    /// changing anything if the dimensions differ or any id already exists here.
+    pub fn merge(&mut self, other: VectorCollection) -> Result<(), ZyphyrError> {
+        self.check_merge_dimensions(&other)?;
+        if let Some(id) = other.ids().find(|id| self.contains(id)) {
+            return Err(ZyphyrError::Other(format!("Duplicate ID: {}", id)));
+        }
+        
+        self.reserve(other.len());
+        for vector in other.into_live_vectors() {
+            self.insert(vector)?;
+        }
+        Ok(())
+    }
+
+    /// Like [`VectorCollection::merge`], but colliding ids from `other` are
+    /// prefixed with `prefix` (repeatedly, until unique) instead of failing
+    pub fn merge_with_rename(&mut self, other: VectorCollection, prefix: &str) -> Result<(), ZyphyrError> {
+        self.check_merge_dimensions(&other)?;
+        if prefix.is_empty() {
+            return Err(ZyphyrError::Other("Rename prefix must not be empty".to_string()));
+        }
+        
+        self.reserve(other.len());
+        for mut vector in other.into_live_vectors() {
+            if self.contains(vector.id()) {
+                let mut id = format!("{}{}", prefix, vector.id());
+                while self.contains(&id) {
+                    id = format!("{}{}", prefix, id);
+                }
+                vector.set_id(id);
+            }
+            self.insert(vector)?;
+        }
+        Ok(())
+    }
+
+    fn check_merge_dimensions(&self, other: &VectorCollection) -> Result<(), ZyphyrError> {
+        if let (Some(ours), Some(theirs)) = (self.dimensions, other.dimensions)
+            && ours != theirs
+            && !other.is_empty()
+        {
+            return Err(ZyphyrError::InvalidDimension { expected: ours, got: theirs });
+        }
+        Ok(())
+    }
+
+    // Consume the collection, yielding only vectors that weren't soft-removed
+    fn into_live_vectors(self) -> impl Iterator<Item = Vector> {
+        self.vectors
+            .into_iter()
+            .zip(self.deleted)
+            .filter(|(_, deleted)| !deleted)
+            .map(|(v, _)| v)
+    }
+
+    /// Remove every vector while keeping the allocated capacity for reuse.
+    /// The dimension is forgotten unless it was pinned by the builder.
+    pub fn clear(&mut self) {
+        self.vectors.clear();
+        self.id_to_index.clear();
+        self.deleted.clear();
+        self.deleted_count = 0;
+        self.memory_bytes = 0;
+        self.dimensions = self.pinned_dimension;
+    }
+
+    /// Like [`VectorCollection::remove`], but reports a missing id as `ZyphyrError::IdNotFound`
+    pub fn try_remove(&mut self, id: &str) -> Result<Vector, ZyphyrError> {
+        self.remove(id).ok_or_else(|| ZyphyrError::IdNotFound(id.to_string()))
+    }
+
+    /// Change a vector's id without moving it. Fails with `ZyphyrError::IdNotFound`
+    /// if `old_id` is missing, `ZyphyrError::Other` if `new_id` already exists.
+    pub fn rename(&mut self, old_id: &str, new_id: &str) -> Result<(), ZyphyrError> {
+        let index = match self.id_to_index.get(old_id) {
+            Some(&index) => index,
+            None => return Err(ZyphyrError::IdNotFound(old_id.to_string())),
+        };
+        if old_id == new_id {
+            return Ok(());
+        }
+        if self.id_to_index.contains_key(new_id) {
+            return Err(ZyphyrError::Other(format!("Duplicate ID: {}", new_id)));
+        }
+        
+        self.id_to_index.remove(old_id);
+        self.id_to_index.insert(new_id.to_string(), index);
+        let vector = &mut self.vectors[index];
+        self.memory_bytes = self.memory_bytes.saturating_sub(vector.memory_usage());
+        vector.set_id(new_id.to_string());
+        self.memory_bytes += vector.memory_usage();
+        Ok(())
+    }
+
+    /// The `k` vectors nearest to `query`, sorted by ascending distance with
+    /// ties broken by id. A query whose dimension differs from the collection's
+    /// fails up front with `ZyphyrError::InvalidDimension`.
     pub fn search(
         &self,
         query: &Vector,
         k: usize,
         metric: DistanceMetric,
     ) -> Result<Vec<(String, f32)>, ZyphyrError> {
-        let mut results: Vec<(String, f32)> = self
-            .vectors
+        self.search_filtered(query, k, metric, |_| true)
+    }
+
+    /// Run several queries at once, returning one result list per query in order.
+    /// Stored vector norms are cached on insert, so cosine batches only pay for
+    /// each query's norm once.
+    pub fn batch_search(
+        &self,
+        queries: &[&Vector],
+        k: usize,
+        metric: DistanceMetric,
+    ) -> Result<Vec<Vec<(String, f32)>>, ZyphyrError> {
+        queries
+            .iter()
+            .map(|query| self.search(query, k, metric.clone()))
+            .collect()
+    }
+
+    /// Like [`VectorCollection::batch_search`], but each query carries its own
+    /// metric, e.g. to compare metrics side by side. The collection is scanned
+    /// once for the whole batch, scoring every query against each stored vector.
+    pub fn batch_search_metrics(
+        &self,
+        queries: &[(&Vector, DistanceMetric)],
+        k: usize,
+    ) -> Result<Vec<Vec<(String, f32)>>, ZyphyrError> {
+        let prepared = queries
             .iter()
-            .map(|v| {
-                let distance = metric.compute(query, v)?;
-                Ok((v.id().to_string(), distance))
+            .map(|(query, metric)| self.prepare_query(query, metric))
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        let mut tops: Vec<TopK> = queries.iter().map(|_| TopK::new(k)).collect();
+        for (position, v) in self.live_entries() {
+            for (((query, fast_cosine), (_, metric)), top) in prepared.iter().zip(queries).zip(&mut tops) {
+                let distance = scan_distance(query, v, metric, *fast_cosine)?;
+                top.push(RankedHit { distance, id: v.id(), position, tie_break: TieBreak::ById, descending: false });
+            }
+        }
+        Ok(tops.into_iter().map(TopK::into_sorted_results).collect())
+    }
+
+    /// Distances from every query to every stored vector: row `i` holds the
+    /// distances from `queries[i]` in [`VectorCollection::iter`] order
+    pub fn distance_matrix(
+        &self,
+        queries: &[&Vector],
+        metric: DistanceMetric,
+    ) -> Result<Vec<Vec<f32>>, ZyphyrError> {
+        for query in queries {
+            self.check_query_dimension(query)?;
+        }
+        
+        let stored: Vec<&Vector> = self.iter().collect();
+        queries
+            .iter()
+            .map(|query| query.batch_distance(&stored, metric.clone()))
+            .collect()
+    }
+
+    /// Search using the collection's configured default metric
+    pub fn search_default(&self, query: &Vector, k: usize) -> Result<Vec<(String, f32)>, ZyphyrError> {
+        self.search(query, k, self.default_metric.clone())
+    }
+
+    /// Search only among vectors accepted by `predicate`. If fewer than `k`
+    /// vectors pass the filter, all of them are returned.
+    pub fn search_filtered(
+        &self,
+        query: &Vector,
+        k: usize,
+        metric: DistanceMetric,
+        predicate: impl Fn(&Vector) -> bool,
+    ) -> Result<Vec<(String, f32)>, ZyphyrError> {
+        self.select_top_k(query, k, metric, self.live_entries().filter(|(_, v)| predicate(v)), SearchOptions::default(), false)
+    }
+
+    /// Like [`VectorCollection::search`], with control over how equal distances are ordered
+    pub fn search_with_options(
+        &self,
+        query: &Vector,
+        k: usize,
+        metric: DistanceMetric,
+        options: &SearchOptions,
+    ) -> Result<Vec<(String, f32)>, ZyphyrError> {
+        self.select_top_k(query, k, metric, self.live_entries(), *options, false)
+    }
+
+    /// Like [`VectorCollection::search`], but with the metric fixed at compile
+    /// time, e.g. `search_generic::<Euclidean>(..)`, so the scan loop is
+    /// monomorphized rather than dispatching on [`DistanceMetric`] per vector
+    pub fn search_generic<D: Distance>(
+        &self,
+        query: &Vector,
+        k: usize,
+    ) -> Result<Vec<(String, f32)>, ZyphyrError> {
+        let (query, fast_cosine) = match D::metric() {
+            Some(metric) => self.prepare_query(query, &metric)?,
+            None => {
+                self.check_query_dimension(query)?;
+                (Cow::Borrowed(query), false)
+            }
+        };
+        
+        let mut top = TopK::new(k);
+        for (position, v) in self.live_entries() {
+            // Stored unit vectors reduce cosine to a dot product, as in `scan_distance`
+            let distance = if fast_cosine && v.is_normalized() {
+                1.0 - DotProduct::distance(query.raw_data(), v.raw_data())
+            } else {
+                D::distance(query.raw_data(), v.raw_data())
+            };
+            top.push(RankedHit { distance, id: v.id(), position, tie_break: TieBreak::ById, descending: false });
+        }
+        Ok(top.into_sorted_results())
+    }
+
+    /// The `k` vectors *farthest* from `query`, sorted by descending distance
+    /// (ties still broken by ascending id)
+    pub fn search_farthest(
+        &self,
+        query: &Vector,
+        k: usize,
+        metric: DistanceMetric,
+    ) -> Result<Vec<(String, f32)>, ZyphyrError> {
+        self.select_top_k(query, k, metric, self.live_entries(), SearchOptions::default(), true)
+    }
+
+    /// Maximum inner product search: the `k` vectors with the *largest* dot
+    /// product against `query`, highest score first.
+    ///
+    /// Beware that `search(query, k, DistanceMetric::DotProduct)` does the
+    /// opposite. Every metric is treated as a distance and sorted ascending, so
+    /// it returns the *lowest* dot products. Use this method when bigger means
+    /// more similar.
+    pub fn search_mips(&self, query: &Vector, k: usize) -> Result<Vec<(String, f32)>, ZyphyrError> {
+        self.select_top_k(query, k, DistanceMetric::DotProduct, self.live_entries(), SearchOptions::default(), true)
+    }
+
+    /// Approximate search that scans only the first `max_candidates` live
+    /// vectors in storage order and returns the best `k` among them. This is a
+    /// deterministic partial scan, useful when data was inserted roughly in
+    /// order of relevance; with `max_candidates >= len()` it equals `search`.
+    pub fn search_with_budget(
+        &self,
+        query: &Vector,
+        k: usize,
+        metric: DistanceMetric,
+        max_candidates: usize,
+    ) -> Result<Vec<(String, f32)>, ZyphyrError> {
+        self.select_top_k(query, k, metric, self.live_entries().take(max_candidates), SearchOptions::default(), false)
+    }
+
+    /// Like [`VectorCollection::search`], but drops hits farther than
+    /// `max_distance` (inclusive bound), so it may return fewer than `k`
+    /// results, or none at all.
+    pub fn search_thresholded(
+        &self,
+        query: &Vector,
+        k: usize,
+        metric: DistanceMetric,
+        max_distance: f32,
+    ) -> Result<Vec<(String, f32)>, ZyphyrError> {
+        // Discarding out-of-range hits before selection keeps the same top-k
+        // while sparing them the heap
+        let mut top = TopK::new(k);
+        self.scan_distances(query, &metric, self.live_entries(), true, |position, v, distance| {
+            if distance <= max_distance {
+                top.push(RankedHit { distance, id: v.id(), position, tie_break: TieBreak::ById, descending: false });
+            }
+        })?;
+        Ok(top.into_sorted_results())
+    }
+
+    /// Distribution of the distances from `query` to every stored vector, as
+    /// `bins` equal-width buckets spanning the smallest to the largest distance.
+    /// Each entry is `(bin_upper_bound, count)`; the last bound is the largest
+    /// distance, and counts sum to [`VectorCollection::len`]. Useful for picking
+    /// a [`VectorCollection::search_thresholded`] cutoff. An empty collection
+    /// yields no bins.
+    pub fn distance_histogram(
+        &self,
+        query: &Vector,
+        metric: DistanceMetric,
+        bins: usize,
+    ) -> Result<Vec<(f32, usize)>, ZyphyrError> {
+        if bins == 0 {
+            return Err(ZyphyrError::Other("a histogram needs at least one bin".to_string()));
+        }
+        
+        let mut distances = Vec::with_capacity(self.len());
+        self.scan_distances(query, &metric, self.live_entries(), true, |_, _, distance| {
+            distances.push(distance);
+        })?;
+        if distances.is_empty() {
+            return Ok(Vec::new());
+        }
+        
+        let min = distances.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = distances.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let width = (max - min) / bins as f32;
+        let mut counts = vec![0usize; bins];
+        for distance in distances {
+            // The maximum lands exactly on the last upper bound, so clamp it into the last bin
+            let bin = if width > 0.0 { ((distance - min) / width) as usize } else { 0 };
+            counts[bin.min(bins - 1)] += 1;
+        }
+        
+        Ok(counts
+            .into_iter()
+            .enumerate()
+            .map(|(i, count)| {
+                let upper = if i + 1 == bins { max } else { min + width * (i + 1) as f32 };
+                (upper, count)
+            })
+            .collect())
+    }
+
+    /// Like [`VectorCollection::search`], but takes the query as a raw slice
+    /// (e.g. an FFI buffer) and scores it without building a [`Vector`]. The
+    /// query is zero-padded into a scratch buffer, and normalized there for
+    /// cosine search on a collection that normalizes on insert, so the same
+    /// kernels run and results are identical to `search` on the same data.
+    pub fn search_slice(
+        &self,
+        query: &[f32],
+        k: usize,
+        metric: DistanceMetric,
+    ) -> Result<Vec<(String, f32)>, ZyphyrError> {
+        if let Some(dims) = self.dimensions
+            && query.len() != dims
+        {
+            return Err(ZyphyrError::InvalidDimension { expected: dims, got: query.len() });
+        }
+        check_finite(query)?;
+        
+        let mut padded = vec![0.0; pad_dimension(query.len(), get_simd_width())];
+        padded[..query.len()].copy_from_slice(query);
+        
+        // Ready the query the way `prepare_query` readies a `Vector`
+        let fast_cosine = self.normalize_on_insert && metric == DistanceMetric::Cosine;
+        let magnitude = l2_norm(query);
+        let query_norm = if fast_cosine {
+            if magnitude > 0.0 {
+                for x in &mut padded[..query.len()] {
+                    *x /= magnitude;
+                }
+            }
+            None
+        } else {
+            Some(magnitude)
+        };
+        
+        let mut top = TopK::new(k);
+        for (position, v) in self.live_entries() {
+            let distance = scan_distance_padded(&padded, query_norm, v, &metric, fast_cosine)?;
+            top.push(RankedHit { distance, id: v.id(), position, tie_break: TieBreak::ById, descending: false });
+        }
+        Ok(top.into_sorted_results())
+    }
+
+    /// Like [`VectorCollection::search`], but writes the hits into `scratch` and
+    /// returns them as a borrowed slice. Reusing one scratch across queries
+    /// recycles its candidate buffer and result strings instead of allocating
+    /// fresh ones per call. Results are identical to `search`.
+    pub fn search_into<'s>(
+        &self,
+        query: &Vector,
+        k: usize,
+        metric: DistanceMetric,
+        scratch: &'s mut SearchScratch,
+    ) -> Result<&'s [(String, f32)], ZyphyrError> {
+        let candidates = &mut scratch.candidates;
+        candidates.clear();
+        self.scan_distances(query, &metric, self.live_entries(), true, |position, _, distance| {
+            candidates.push((distance, position));
+        })?;
+        
+        let by_rank = |a: &(f32, usize), b: &(f32, usize)| {
+            compare_results(&a.0, self.vectors[a.1].id(), &b.0, self.vectors[b.1].id())
+        };
+        let k = k.min(candidates.len());
+        if k > 0 && k < candidates.len() {
+            candidates.select_nth_unstable_by(k - 1, by_rank);
+        }
+        candidates[..k].sort_unstable_by(by_rank);
+        
+        // Overwrite the previous query's id strings in place so their buffers are reused
+        let results = &mut scratch.results;
+        results.truncate(k);
+        for (i, &(distance, position)) in candidates[..k].iter().enumerate() {
+            let id = self.vectors[position].id();
+            match results.get_mut(i) {
+                Some(slot) => {
+                    slot.0.clear();
+                    slot.0.push_str(id);
+                    slot.1 = distance;
+                }
+                None => results.push((id.to_string(), distance)),
+            }
+        }
+        Ok(&scratch.results[..k])
+    }
+
+    // Shared brute-force scan behind the serial search variants over `entries`
+    // (position, vector) pairs; `descending` keeps the largest distances instead of the smallest
+    fn select_top_k<'a>(
+        &self,
+        query: &Vector,
+        k: usize,
+        metric: DistanceMetric,
+        entries: impl Iterator<Item = (usize, &'a Vector)>,
+        options: SearchOptions,
+        descending: bool,
+    ) -> Result<Vec<(String, f32)>, ZyphyrError> {
+        let tie_break = options.tie_break;
+        let mut top = TopK::new(k);
+        self.scan_distances(query, &metric, entries, options.prefetch, |position, v, distance| {
+            top.push(RankedHit { distance, id: v.id(), position, tie_break, descending });
+        })?;
+        Ok(top.into_sorted_results())
+    }
+
+    // Score `query` against every entry, handing `(position, vector, distance)` to `visit`
+    fn scan_distances<'a>(
+        &self,
+        query: &Vector,
+        metric: &DistanceMetric,
+        entries: impl Iterator<Item = (usize, &'a Vector)>,
+        prefetch: bool,
+        mut visit: impl FnMut(usize, &'a Vector, f32),
+    ) -> Result<(), ZyphyrError> {
+        let (query, fast_cosine) = self.prepare_query(query, metric)?;
+        let query = query.as_ref();
+        
+        let mut entries = entries.peekable();
+        while let Some((position, v)) = entries.next() {
+            // Each vector is its own allocation, so start loading the next one
+            // while this one is scored
+            if prefetch
+                && let Some((_, next)) = entries.peek()
+            {
+                prefetch_read(next.raw_data());
+            }
+            visit(position, v, scan_distance(query, v, metric, fast_cosine)?);
+        }
+        Ok(())
+    }
+
+    // Check the query's dimension and ready it for a scan under `metric`. The flag
+    // is set when stored vectors are unit length, so cosine distance reduces to
+    // 1 - dot product and the query is normalized once up front.
+    fn prepare_query<'q>(
+        &self,
+        query: &'q Vector,
+        metric: &DistanceMetric,
+    ) -> Result<(Cow<'q, Vector>, bool), ZyphyrError> {
+        self.check_query_dimension(query)?;
+        
+        if self.normalize_on_insert && *metric == DistanceMetric::Cosine {
+            let mut query = query.clone();
+            query.normalize();
+            Ok((Cow::Owned(query), true))
+        } else {
+            Ok((with_cached_norm(query, metric), false))
+        }
+    }
+
+    /// Return every vector within `radius` of `query` (inclusive), sorted by ascending distance
+    pub fn range_search(
+        &self,
+        query: &Vector,
+        radius: f32,
+        metric: DistanceMetric,
+    ) -> Result<Vec<(String, f32)>, ZyphyrError> {
+        let query = with_cached_norm(query, &metric);
+        let mut results = Vec::new();
+        for v in self.iter() {
+            let distance = metric.compute(&query, v)?;
+            if distance <= radius {
+                results.push((v.id().to_string(), distance));
+            }
+        }
+        results.sort_by(|a, b| compare_results(&a.1, &a.0, &b.1, &b.0));
+        Ok(results)
+    }
+
+    /// Multi-threaded brute-force search. Distances are computed across the rayon
+    /// thread pool and the top-k are selected with a bounded heap instead of a full
+    /// sort. Results are identical to [`VectorCollection::search`].
+    #[cfg(feature = "rayon")]
+    pub fn search_parallel(
+        &self,
+        query: &Vector,
+        k: usize,
+        metric: DistanceMetric,
+    ) -> Result<Vec<(String, f32)>, ZyphyrError> {
+        use rayon::prelude::*;
+        
+        let (query, fast_cosine) = self.prepare_query(query, &metric)?;
+        let query = query.as_ref();
+        let distances: Vec<f32> = self
+            .vectors
+            .par_iter()
+            .zip(self.deleted.par_iter())
+            .map(|(v, &deleted)| {
+                if deleted {
+                    Ok(f32::NAN)
+                } else {
+                    scan_distance(query, v, &metric, fast_cosine)
+                }
             })
             .collect::<Result<Vec<_>, ZyphyrError>>()?;
-        results.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
-        Ok(results.into_iter().take(k).collect())
+        
+        let mut top = TopK::new(k);
+        for (position, vector) in self.live_entries() {
+            let distance = distances[position];
+            top.push(RankedHit {
+                distance,
+                id: vector.id(),
+                position,
+                tie_break: TieBreak::ById,
+                descending: false,
+            });
+        }
+        Ok(top.into_sorted_results())
+    }
+
+    /// Same as [`VectorCollection::search`], but also returns a clone of each hit's metadata
+    pub fn search_with_metadata(
+        &self,
+        query: &Vector,
+        k: usize,
+        metric: DistanceMetric,
+    ) -> Result<Vec<(String, f32, Option<Metadata>)>, ZyphyrError> {
+        let results = self.search(query, k, metric)?;
+        Ok(results
+            .into_iter()
+            .map(|(id, distance)| {
+                let metadata = self.get(&id).and_then(|v| v.metadata().cloned());
+                (id, distance, metadata)
+            })
+            .collect())
+    }
+
+    /// Cluster the collection into `k` groups with Lloyd's algorithm and k-means++
+    /// seeding, returning the member ids of each cluster. The same `seed` always
+    /// gives the same clusters for an unchanged collection.
+    pub fn kmeans(
+        &self,
+        k: usize,
+        max_iters: usize,
+        metric: DistanceMetric,
+        seed: u64,
+    ) -> Result<Vec<Vec<String>>, ZyphyrError> {
+        use rand::SeedableRng;
+        
+        let points: Vec<&[f32]> = self.iter().map(|v| v.data()).collect();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let clustering = crate::index::kmeans::kmeans(&points, k, max_iters, &metric, &mut rng)?;
+        
+        let mut clusters = vec![Vec::new(); k];
+        for (vector, &cluster) in self.iter().zip(clustering.assignments.iter()) {
+            clusters[cluster].push(vector.id().to_string());
+        }
+        Ok(clusters)
+    }
+
+    /// Pick `n` vectors uniformly at random with reservoir sampling (one pass, O(n)
+    /// memory), e.g. to train IVF or PQ centroids on a subset. The same seed always
+    /// selects the same subset of an unchanged collection. If `n >= len()` every
+    /// vector is returned.
+    pub fn sample(&self, n: usize, seed: u64) -> Vec<&Vector> {
+        use rand::{Rng, SeedableRng};
+        
+        if n >= self.len() {
+            return self.iter().collect();
+        }
+        
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let mut reservoir: Vec<&Vector> = Vec::with_capacity(n);
+        for (seen, vector) in self.iter().enumerate() {
+            if seen < n {
+                reservoir.push(vector);
+            } else {
+                let slot = rng.random_range(0..=seen);
+                if slot < n {
+                    reservoir[slot] = vector;
+                }
+            }
+        }
+        reservoir
+    }
+
+    /// Count, mean, per-dimension range and average L2 norm of the stored vectors,
+    /// useful for spotting un-normalized or degenerate embeddings
+    pub fn stats(&self) -> CollectionStats {
+        let count = self.len();
+        let dim = match self.dimensions {
+            Some(dim) if count > 0 => dim,
+            _ => {
+                return CollectionStats { count, mean: None, min: None, max: None, average_norm: None };
+            }
+        };
+        
+        let mut sum = vec![0.0f64; dim];
+        let mut min = vec![f32::INFINITY; dim];
+        let mut max = vec![f32::NEG_INFINITY; dim];
+        let mut norm_sum = 0.0f64;
+        for v in self.iter() {
+            for (d, &x) in v.data().iter().enumerate() {
+                sum[d] += x as f64;
+                min[d] = min[d].min(x);
+                max[d] = max[d].max(x);
+            }
+            norm_sum += crate::vector::distance::l2_norm(v.data()) as f64;
+        }
+        
+        CollectionStats {
+            count,
+            mean: Some(sum.into_iter().map(|s| (s / count as f64) as f32).collect()),
+            min: Some(min),
+            max: Some(max),
+            average_norm: Some((norm_sum / count as f64) as f32),
+        }
+    }
+
+    /// Scale every stored vector to unit length in place with [`Vector::normalize`],
+    /// in parallel with the `rayon` feature. Zero vectors stay zero. Afterwards
+    /// cosine distance ranks exactly like the (negated) dot product.
+    pub fn normalize_all(&mut self) {
+        let normalize = |v: &mut Vector| {
+            v.normalize();
+            // Re-cache the norm that normalizing invalidated
+            v.norm();
+        };
+        
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            self.vectors.par_iter_mut().for_each(normalize);
+        }
+        #[cfg(not(feature = "rayon"))]
+        self.vectors.iter_mut().for_each(normalize);
+    }
+
+    /// Subtract the corpus mean from every stored vector and return the mean
+    /// (with id `"mean"`), so queries can be centered the same way with
+    /// [`Vector::sub`]. Ids and metadata are kept; a collection that normalizes
+    /// on insert re-normalizes the centered vectors. Fails on an empty collection.
+    pub fn center(&mut self) -> Result<Vector, ZyphyrError> {
+        let mean = match self.stats().mean {
+            Some(mean) => Vector::new("mean", mean)?,
+            None => return Err(ZyphyrError::Other("cannot center an empty collection".to_string())),
+        };
+        
+        for index in 0..self.vectors.len() {
+            if self.deleted[index] {
+                continue;
+            }
+            let old = &self.vectors[index];
+            let mut centered = old.sub(&mean, old.id())?;
+            if let Some(metadata) = old.metadata() {
+                centered = centered.with_metadata(metadata.clone());
+            }
+            if self.normalize_on_insert {
+                centered.normalize();
+            }
+            centered.norm();
+            
+            self.memory_bytes = self.memory_bytes.saturating_sub(old.memory_usage()) + centered.memory_usage();
+            self.vectors[index] = centered;
+        }
+        Ok(mean)
+    }
+
+    /// Every pair of ids whose vectors are closer than `threshold` under `metric`.
+    /// Each pair is reported once, earlier-stored id first.
+    pub fn find_duplicates(
+        &self,
+        threshold: f32,
+        metric: DistanceMetric,
+    ) -> Result<Vec<(String, String)>, ZyphyrError> {
+        // Naive O(n²) pairwise scan. For large collections, a range query per vector
+        // against an ANN index (e.g. HnswIndex) would avoid comparing every pair.
+        let vectors: Vec<&Vector> = self.iter().collect();
+        let mut pairs = Vec::new();
+        for (i, a) in vectors.iter().enumerate() {
+            for b in &vectors[i + 1..] {
+                if metric.compute(a, b)? < threshold {
+                    pairs.push((a.id().to_string(), b.id().to_string()));
+                }
+            }
+        }
+        Ok(pairs)
+    }
+
+    /// Element-wise mean of the named vectors, returned as a new vector with id "centroid"
+    pub fn centroid(&self, ids: &[&str]) -> Result<Vector, ZyphyrError> {
+        if ids.is_empty() {
+            return Err(ZyphyrError::Other("Cannot compute the centroid of an empty id list".to_string()));
+        }
+        
+        let mut sum: Option<Vec<f32>> = None;
+        for id in ids {
+            let vector = self.try_get(id)?;
+            match sum.as_mut() {
+                Some(sum) => {
+                    for (acc, &x) in sum.iter_mut().zip(vector.data()) {
+                        *acc += x;
+                    }
+                }
+                None => sum = Some(vector.data().to_vec()),
+            }
+        }
+        
+        let count = ids.len() as f32;
+        let mean = sum.unwrap_or_default().into_iter().map(|x| x / count).collect();
+        Vector::new("centroid", mean)
+    }
+
+    pub fn default_metric(&self) -> DistanceMetric {
+        self.default_metric.clone()
     }
 
+    /// Whether inserted vectors are normalized to unit length
+    pub fn normalizes_on_insert(&self) -> bool {
+        self.normalize_on_insert
+    }
+
+    pub fn dimensions(&self) -> Option<usize> {
+        self.dimensions
+    }
+
+    /// Number of live vectors, excluding soft-removed ones
     pub fn len(&self) -> usize {
+        self.vectors.len() - self.deleted_count
+    }
+
+    /// Number of physical slots, including soft-removed ones awaiting [`VectorCollection::compact`]
+    pub fn raw_len(&self) -> usize {
         self.vectors.len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.vectors.is_empty()
+        self.len() == 0
+    }
+
+    /// Write the collection to `path` in a compact little-endian binary format:
+    /// magic, dimension, count, then each vector's id and unpadded f32 data.
+    /// Metadata is not part of the format and is dropped; use
+    /// `to_json` or `to_bincode` to keep it.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ZyphyrError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        
+        writer.write_all(FILE_MAGIC)?;
+        writer.write_all(&(self.dimensions.unwrap_or(0) as u64).to_le_bytes())?;
+        writer.write_all(&(self.len() as u64).to_le_bytes())?;
+        
+        for vector in self.iter() {
+            let id = vector.id().as_bytes();
+            writer.write_all(&(id.len() as u32).to_le_bytes())?;
+            writer.write_all(id)?;
+            for value in vector.data() {
+                writer.write_all(&value.to_le_bytes())?;
+            }
+        }
+        
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Read a collection previously written by [`VectorCollection::save`]. The
+    /// format carries no metadata, so every loaded vector has `metadata() == None`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ZyphyrError> {
+        let file = File::open(path)?;
+        let file_len = file.metadata()?.len();
+        let mut reader = BufReader::new(file);
+        
+        let mut magic = [0u8; 4];
+        read_exact_or_corrupt(&mut reader, &mut magic)?;
+        if &magic != FILE_MAGIC {
+            return Err(ZyphyrError::Corrupt("invalid file header".to_string()));
+        }
+        
+        let dim = read_u64(&mut reader)?;
+        let count = read_u64(&mut reader)?;
+        if count > 0 && dim == 0 {
+            return Err(ZyphyrError::Corrupt("non-empty collection with zero dimension".to_string()));
+        }
+        
+        // Header fields are untrusted: check that the file can actually hold `count`
+        // records before allocating for them. The bytes left over bound the ids.
+        let body_len = file_len.saturating_sub((FILE_MAGIC.len() + 16) as u64);
+        let mut id_budget = dim
+            .checked_mul(4)
+            .and_then(|data_len| data_len.checked_add(4))
+            .and_then(|record_len| record_len.checked_mul(count))
+            .and_then(|records_len| body_len.checked_sub(records_len))
+            .ok_or_else(|| ZyphyrError::Corrupt("vector count exceeds file size".to_string()))?;
+        let (dim, count) = (dim as usize, count as usize);
+        
+        let mut collection = VectorCollection::with_capacity(count);
+        let mut value = [0u8; 4];
+        for _ in 0..count {
+            let mut len = [0u8; 4];
+            read_exact_or_corrupt(&mut reader, &mut len)?;
+            let id_len = u32::from_le_bytes(len) as u64;
+            id_budget = id_budget
+                .checked_sub(id_len)
+                .ok_or_else(|| ZyphyrError::Corrupt("vector id exceeds file size".to_string()))?;
+            let mut id = vec![0u8; id_len as usize];
+            read_exact_or_corrupt(&mut reader, &mut id)?;
+            let id = String::from_utf8(id)
+                .map_err(|_| ZyphyrError::Corrupt("vector id is not valid UTF-8".to_string()))?;
+            
+            let mut data = Vec::with_capacity(dim);
+            for _ in 0..dim {
+                read_exact_or_corrupt(&mut reader, &mut value)?;
+                data.push(f32::from_le_bytes(value));
+            }
+            collection.insert(Vector::new(id, data)?)?;
+        }
+        
+        Ok(collection)
+    }
+
+    /// Export all vectors as a JSON array of `{"id": ..., "data": [...]}` objects
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Result<String, ZyphyrError> {
+        let records: Vec<VectorRecord> = self
+            .iter()
+            .map(|v| VectorRecord {
+                id: v.id().to_string(),
+                data: v.data().to_vec(),
+                metadata: v.metadata().cloned(),
+            })
+            .collect();
+        serde_json::to_string(&records).map_err(|e| ZyphyrError::Serialization(e.to_string()))
     }
+
+    /// Build a collection from JSON produced by [`VectorCollection::to_json`]
+    #[cfg(feature = "serde")]
+    pub fn from_json(s: &str) -> Result<Self, ZyphyrError> {
+        let records: Vec<VectorRecord> =
+            serde_json::from_str(s).map_err(|e| ZyphyrError::Serialization(e.to_string()))?;
+        
+        let mut collection = VectorCollection::with_capacity(records.len());
+        for record in records {
+            let mut vector = Vector::new(record.id, record.data)?;
+            if let Some(metadata) = record.metadata {
+                vector = vector.with_metadata(metadata);
+            }
+            collection.insert(vector)?;
+        }
+        Ok(collection)
+    }
+
+    /// Serialize the live vectors to a compact binary payload: a magic header
+    /// and version byte followed by bincode-encoded `(id, data, metadata)` records
+    #[cfg(feature = "bincode")]
+    pub fn to_bincode(&self) -> Result<Vec<u8>, ZyphyrError> {
+        // Tuples rather than `Vector`'s serde impl, which skips absent metadata
+        // and so isn't decodable by a non-self-describing format
+        let records: Vec<(&str, &[f32], Option<&Metadata>)> =
+            self.iter().map(|v| (v.id(), v.data(), v.metadata())).collect();
+        
+        let mut bytes = Vec::with_capacity(BINCODE_MAGIC.len() + 1);
+        bytes.extend_from_slice(BINCODE_MAGIC);
+        bytes.push(BINCODE_VERSION);
+        let payload = bincode::serde::encode_to_vec(&records, bincode::config::standard())
+            .map_err(|e| ZyphyrError::Serialization(e.to_string()))?;
+        bytes.extend_from_slice(&payload);
+        Ok(bytes)
+    }
+
+    /// Rebuild a collection from [`VectorCollection::to_bincode`] output
+    #[cfg(feature = "bincode")]
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self, ZyphyrError> {
+        let header = BINCODE_MAGIC.len();
+        if bytes.len() <= header || &bytes[..header] != BINCODE_MAGIC {
+            return Err(ZyphyrError::Corrupt("invalid bincode header".to_string()));
+        }
+        if bytes[header] != BINCODE_VERSION {
+            return Err(ZyphyrError::Corrupt(format!("unsupported bincode version {}", bytes[header])));
+        }
+        
+        let payload = &bytes[header + 1..];
+        let (records, read): (Vec<BincodeRecord>, usize) =
+            bincode::serde::decode_from_slice(payload, bincode::config::standard())
+                .map_err(|e| ZyphyrError::Corrupt(e.to_string()))?;
+        if read != payload.len() {
+            return Err(ZyphyrError::Corrupt("trailing bytes after bincode payload".to_string()));
+        }
+        
+        let mut collection = VectorCollection::with_capacity(records.len());
+        for (id, data, metadata) in records {
+            let mut vector = Vector::new(id, data)?;
+            if let Some(metadata) = metadata {
+                vector = vector.with_metadata(metadata);
+            }
+            collection.insert(vector)?;
+        }
+        Ok(collection)
+    }
+}
+
+/// Merge per-shard result lists, each sorted by ascending distance, into one
+/// global top-`k`. An id that appears in several lists is kept once, with its
+/// smallest distance; equal distances are ordered by id.
+pub fn merge_search_results(results: &[Vec<(String, f32)>], k: usize) -> Vec<(String, f32)> {
+    // Min-heap over the head of every list; the second field is the offset into the list
+    let mut heads: BinaryHeap<Reverse<(RankedHit, usize)>> = results
+        .iter()
+        .enumerate()
+        .filter_map(|(list, hits)| hits.first().map(|(id, distance)| (list, id, *distance)))
+        .map(|(list, id, distance)| Reverse((merge_head(list, id, distance), 0)))
+        .collect();
+    
+    let mut seen = HashSet::new();
+    let mut merged = Vec::with_capacity(k.min(results.iter().map(Vec::len).sum()));
+    while merged.len() < k {
+        let Some(Reverse((hit, offset))) = heads.pop() else {
+            break;
+        };
+        // Heads come out in ascending order, so the first sighting of an id is its best
+        if seen.insert(hit.id) {
+            merged.push((hit.id.to_string(), hit.distance));
+        }
+        if let Some((id, distance)) = results[hit.position].get(offset + 1) {
+            heads.push(Reverse((merge_head(hit.position, id, *distance), offset + 1)));
+        }
+    }
+    merged
+}
+
+fn merge_head(list: usize, id: &str, distance: f32) -> RankedHit<'_> {
+    RankedHit { distance, id, position: list, tie_break: TieBreak::ById, descending: false }
+}
+
+// Cosine distance reads cached norms, so give an uncached query one up front
+// rather than recomputing its magnitude against every stored vector
+fn with_cached_norm<'a>(query: &'a Vector, metric: &DistanceMetric) -> Cow<'a, Vector> {
+    if *metric == DistanceMetric::Cosine && query.cached_norm().is_none() {
+        let mut owned = query.clone();
+        owned.norm();
+        Cow::Owned(owned)
+    } else {
+        Cow::Borrowed(query)
+    }
+}
+
+// Distance from a query readied by `prepare_query` to a stored vector
+fn scan_distance(query: &Vector, v: &Vector, metric: &DistanceMetric, fast_cosine: bool) -> Result<f32, ZyphyrError> {
+    scan_distance_padded(query.raw_data(), query.cached_norm(), v, metric, fast_cosine)
+}
+
+// `scan_distance` over a zero-padded query buffer and its norm, if cached
+fn scan_distance_padded(
+    query: &[f32],
+    query_norm: Option<f32>,
+    v: &Vector,
+    metric: &DistanceMetric,
+    fast_cosine: bool,
+) -> Result<f32, ZyphyrError> {
+    // The shortcut is only sound for vectors that are still unit length
+    if fast_cosine && v.is_normalized() {
+        Ok(1.0 - DistanceMetric::DotProduct.compute_padded(query, None, v)?)
+    } else {
+        metric.compute_padded(query, query_norm, v)
+    }
+}
+
+// Order search hits by ascending distance, breaking ties by id so results are deterministic
+fn compare_results(a_distance: &f32, a_id: &str, b_distance: &f32, b_id: &str) -> Ordering {
+    a_distance
+        .partial_cmp(b_distance)
+        .unwrap_or(Ordering::Equal)
+        .then_with(|| a_id.cmp(b_id))
+}
+
+/// Borrowed search hit used for heap-based top-k selection
+#[derive(Debug, Clone, Copy)]
+struct RankedHit<'a> {
+    distance: f32,
+    id: &'a str,
+    position: usize,  // Storage position (or source list when merging), used by TieBreak::ByInsertionOrder
+    tie_break: TieBreak,
+    descending: bool, // Rank larger distances first (farthest-neighbor search)
+}
+
+impl PartialEq for RankedHit<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for RankedHit<'_> {}
+
+impl PartialOrd for RankedHit<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RankedHit<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let by_distance = self.distance.partial_cmp(&other.distance).unwrap_or(Ordering::Equal);
+        let by_distance = if self.descending { by_distance.reverse() } else { by_distance };
+        by_distance.then_with(|| match self.tie_break {
+            TieBreak::ById => self.id.cmp(other.id),
+            TieBreak::ByInsertionOrder => self.position.cmp(&other.position),
+        })
+    }
+}
+
+/// Bounded max-heap keeping the `k` best hits seen so far; the root is the worst of them.
+/// Selection is O(n log k) rather than the O(n log n) of sorting every distance.
+struct TopK<'a> {
+    heap: BinaryHeap<RankedHit<'a>>,
+    k: usize,
+}
+
+impl<'a> TopK<'a> {
+    fn new(k: usize) -> Self {
+        // Cap the up-front allocation so callers can pass a huge k to fetch everything
+        TopK { heap: BinaryHeap::with_capacity(k.saturating_add(1).min(1024)), k }
+    }
+
+    fn push(&mut self, hit: RankedHit<'a>) {
+        if self.heap.len() < self.k {
+            self.heap.push(hit);
+        } else if let Some(worst) = self.heap.peek()
+            && hit < *worst
+        {
+            self.heap.pop();
+            self.heap.push(hit);
+        }
+    }
+
+    fn into_sorted_results(self) -> Vec<(String, f32)> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|hit| (hit.id.to_string(), hit.distance))
+            .collect()
+    }
+}
+
+// Treat a short read as a malformed file rather than a generic IO failure
+fn read_exact_or_corrupt<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<(), ZyphyrError> {
+    reader.read_exact(buf).map_err(|e| match e.kind() {
+        std::io::ErrorKind::UnexpectedEof => ZyphyrError::Corrupt("unexpected end of file".to_string()),
+        _ => ZyphyrError::Io(e),
+    })
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64, ZyphyrError> {
+    let mut buf = [0u8; 8];
+    read_exact_or_corrupt(reader, &mut buf)?;
+    Ok(u64::from_le_bytes(buf))
 }
\ No newline at end of file
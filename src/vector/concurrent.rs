@@ -0,0 +1,98 @@
+use crate::{Vector, VectorCollection, ZyphyrError, DistanceMetric};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+// VectorCollection holds only owned, immutable-through-&self data, so it is
+// automatically Send + Sync; keep that guaranteed at compile time.
+const _: () = {
+    fn assert_send_sync<T: Send + Sync>() {}
+    let _ = assert_send_sync::<VectorCollection>;
+};
+
+/// Thread-safe wrapper around [`VectorCollection`] that allows many concurrent
+/// readers while serializing writers.
+///
+/// Read lock: `search`, `get`, `contains`, `len`, `is_empty`, `read`.
+/// Write lock: `insert`, `upsert`, `remove`, `write`.
+///
+/// A panic while the write lock is held poisons the wrapper: the collection may
+/// be half-updated, so every later call (and `into_inner`) fails with
+/// `ZyphyrError::Corrupt` instead of handing it out.
+pub struct ConcurrentCollection {
+    inner: RwLock<VectorCollection>,
+}
+
+impl Default for ConcurrentCollection {
+    fn default() -> Self {
+        Self::new(VectorCollection::new())
+    }
+}
+
+impl From<VectorCollection> for ConcurrentCollection {
+    fn from(collection: VectorCollection) -> Self {
+        Self::new(collection)
+    }
+}
+
+impl ConcurrentCollection {
+    pub fn new(collection: VectorCollection) -> Self {
+        ConcurrentCollection {
+            inner: RwLock::new(collection),
+        }
+    }
+
+    pub fn search(
+        &self,
+        query: &Vector,
+        k: usize,
+        metric: DistanceMetric,
+    ) -> Result<Vec<(String, f32)>, ZyphyrError> {
+        self.read()?.search(query, k, metric)
+    }
+
+    /// Clone of the vector with the given id
+    pub fn get(&self, id: &str) -> Result<Option<Vector>, ZyphyrError> {
+        Ok(self.read()?.get(id).cloned())
+    }
+
+    pub fn contains(&self, id: &str) -> Result<bool, ZyphyrError> {
+        Ok(self.read()?.contains(id))
+    }
+
+    pub fn len(&self) -> Result<usize, ZyphyrError> {
+        Ok(self.read()?.len())
+    }
+
+    pub fn is_empty(&self) -> Result<bool, ZyphyrError> {
+        Ok(self.read()?.is_empty())
+    }
+
+    pub fn insert(&self, vector: Vector) -> Result<(), ZyphyrError> {
+        self.write()?.insert(vector)
+    }
+
+    pub fn upsert(&self, vector: Vector) -> Result<bool, ZyphyrError> {
+        self.write()?.upsert(vector)
+    }
+
+    pub fn remove(&self, id: &str) -> Result<Option<Vector>, ZyphyrError> {
+        Ok(self.write()?.remove(id))
+    }
+
+    /// Shared access for operations not wrapped here. Holding the guard blocks writers.
+    pub fn read(&self) -> Result<RwLockReadGuard<'_, VectorCollection>, ZyphyrError> {
+        self.inner.read().map_err(|_| poisoned())
+    }
+
+    /// Exclusive access for operations not wrapped here
+    pub fn write(&self) -> Result<RwLockWriteGuard<'_, VectorCollection>, ZyphyrError> {
+        self.inner.write().map_err(|_| poisoned())
+    }
+
+    pub fn into_inner(self) -> Result<VectorCollection, ZyphyrError> {
+        self.inner.into_inner().map_err(|_| poisoned())
+    }
+}
+
+fn poisoned() -> ZyphyrError {
+    ZyphyrError::Corrupt("ConcurrentCollection lock poisoned by a panicked writer".to_string())
+}
@@ -1,13 +1,82 @@
-use crate::{Vector, ZyphyrError};
+use crate::{Vector, VectorElement, ZyphyrError};
+use std::fmt;
+use std::sync::Arc;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Signature of a user-supplied distance for [`DistanceMetric::Custom`]
+pub type CustomDistanceFn = dyn Fn(&[f32], &[f32]) -> f32 + Send + Sync;
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DistanceMetric {
     Euclidean,
     Cosine,
+    /// The raw dot product. Note this is a *similarity*: ordinary searches sort
+    /// ascending and so return the smallest dot products. Use
+    /// `VectorCollection::search_mips` for maximum inner product search.
     DotProduct,
+    /// Euclidean distance with each squared difference scaled by a per-dimension weight.
+    /// The weights are shared behind an `Arc` so cloning the metric stays cheap.
+    /// Weights must be finite and non-negative. [`DistanceMetric::weighted_euclidean`]
+    /// checks that once up front; building the variant directly skips the check,
+    /// and distance calls only check the weight count.
+    WeightedEuclidean { weights: Arc<Vec<f32>> },
+    /// Symmetric Jensen-Shannon divergence (base 2, so in [0, 1]) between
+    /// non-negative vectors, each normalized to sum to 1 first
+    JensenShannon,
+    /// One minus the Pearson correlation: compares shape, ignoring offset and scale
+    Correlation,
+    /// Angle between the vectors in radians, in [0, π]. Unlike cosine distance this
+    /// is a true metric (it satisfies the triangle inequality).
+    Angular,
+    /// One minus the Tanimoto coefficient `a·b / (|a|² + |b|² - a·b)`, which
+    /// generalizes Jaccard distance to real-valued vectors (e.g. chemical
+    /// fingerprints). Two all-zero vectors are at the maximum distance of 1.
+    Tanimoto,
+    /// A caller-supplied distance over the real (unpadded) values, invoked after
+    /// the dimension check. Two custom metrics are equal only if they share the
+    /// same `Arc`. Closures can't be serialized, so serializing this variant fails.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(Arc<CustomDistanceFn>),
+}
+
+impl fmt::Debug for DistanceMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DistanceMetric::Euclidean => f.write_str("Euclidean"),
+            DistanceMetric::Cosine => f.write_str("Cosine"),
+            DistanceMetric::DotProduct => f.write_str("DotProduct"),
+            DistanceMetric::WeightedEuclidean { weights } => {
+                f.debug_struct("WeightedEuclidean").field("weights", weights).finish()
+            }
+            DistanceMetric::JensenShannon => f.write_str("JensenShannon"),
+            DistanceMetric::Correlation => f.write_str("Correlation"),
+            DistanceMetric::Angular => f.write_str("Angular"),
+            DistanceMetric::Tanimoto => f.write_str("Tanimoto"),
+            DistanceMetric::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+impl PartialEq for DistanceMetric {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (DistanceMetric::WeightedEuclidean { weights: a }, DistanceMetric::WeightedEuclidean { weights: b }) => a == b,
+            // Closures can't be compared, so fall back to identity
+            (DistanceMetric::Custom(a), DistanceMetric::Custom(b)) => Arc::ptr_eq(a, b),
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
 }
 
 impl DistanceMetric {
+    /// A [`DistanceMetric::WeightedEuclidean`] over `weights`. Returns
+    /// `ZyphyrError::InvalidData` if any weight is negative or not finite, since
+    /// either could make the distance NaN.
+    pub fn weighted_euclidean(weights: Vec<f32>) -> Result<Self, ZyphyrError> {
+        check_weights(&weights)?;
+        Ok(DistanceMetric::WeightedEuclidean { weights: Arc::new(weights) })
+    }
+
     pub fn compute(&self, a: &Vector, b: &Vector) -> Result<f32, ZyphyrError> {
         if a.dim() != b.dim() {
             return Err(ZyphyrError::InvalidDimension {
@@ -15,30 +84,185 @@ impl DistanceMetric {
                 got: b.dim(),
             });
         }
+        self.compute_padded(a.raw_data(), a.cached_norm(), b)
+    }
+
+    /// [`DistanceMetric::compute`] with the first operand given as zero-padded
+    /// data of `b`'s padded length and its norm, if cached. Raw slice queries
+    /// go through here so they run exactly the same kernels as `Vector` queries.
+    pub(crate) fn compute_padded(&self, a: &[f32], a_norm: Option<f32>, b: &Vector) -> Result<f32, ZyphyrError> {
+        if a.len() != b.padded_len() {
+            return Err(ZyphyrError::InvalidDimension {
+                expected: b.padded_len(),
+                got: a.len(),
+            });
+        }
+        let a_data = &a[..b.dim()];
         match self {
-            DistanceMetric::Euclidean => Ok(euclidean_distance(a.data(), b.data())),
+            DistanceMetric::Euclidean => Ok(euclidean_distance_padded(a, b.raw_data())),
+            // With both norms cached only the dot product is left to compute; otherwise
+            // the fused pass gets both magnitudes from the same loads as the dot product.
+            // Zero padding leaves every sum unchanged.
+            DistanceMetric::Cosine => match (a_norm, b.cached_norm()) {
+                (Some(a_mag), Some(b_mag)) => {
+                    Ok(1.0 - cosine_similarity_with_norms(a, b.raw_data(), a_mag, b_mag))
+                }
+                _ => Ok(cosine_distance_padded(a, b.raw_data())),
+            },
+            // Zero padding contributes nothing to a dot product, so the padded buffers are safe
+            DistanceMetric::DotProduct => Ok(dot_product_padded(a, b.raw_data())),
+            DistanceMetric::WeightedEuclidean { weights } => weighted_euclidean_distance(a_data, b.data(), weights),
+            DistanceMetric::JensenShannon => jensen_shannon_divergence(a_data, b.data()),
+            DistanceMetric::Correlation => Ok(correlation_distance(a_data, b.data())),
+            DistanceMetric::Angular => {
+                let a_mag = a_norm.unwrap_or_else(|| l2_norm(a_data));
+                let b_mag = b.cached_norm().unwrap_or_else(|| l2_norm(b.data()));
+                Ok(angle(cosine_similarity_with_norms(a_data, b.data(), a_mag, b_mag)))
+            }
+            // Zero padding leaves the dot product and both squared magnitudes unchanged
+            DistanceMetric::Tanimoto => Ok(tanimoto_distance(a, b.raw_data())),
+            DistanceMetric::Custom(distance) => Ok(distance(a_data, b.data())),
+        }
+    }
+
+    /// Distance between vectors of any [`VectorElement`] type. Euclidean, cosine
+    /// and dot product run through the element's own kernels; every other
+    /// metric converts the real dimensions to `f32` and uses [`DistanceMetric::compute_slices`].
+    pub fn compute_generic<T: VectorElement>(&self, a: &Vector<T>, b: &Vector<T>) -> Result<f32, ZyphyrError> {
+        if a.dim() != b.dim() {
+            return Err(ZyphyrError::InvalidDimension {
+                expected: a.dim(),
+                got: b.dim(),
+            });
+        }
+        // Zero padding contributes nothing to these sums, so the padded buffers are safe
+        match self {
+            DistanceMetric::Euclidean => Ok(T::euclidean_distance(a.raw_data(), b.raw_data())),
+            DistanceMetric::DotProduct => Ok(T::dot_product(a.raw_data(), b.raw_data())),
             DistanceMetric::Cosine => {
-                // Calculate cosine similarity directly without modifying original vectors
-                let a_data = a.data();
-                let b_data = b.data();
-                
-                let dot = a_data.iter().zip(b_data.iter()).map(|(x, y)| x * y).sum::<f32>();
-                let a_mag = a_data.iter().map(|x| x * x).sum::<f32>().sqrt();
-                let b_mag = b_data.iter().map(|x| x * x).sum::<f32>().sqrt();
-                
-                // Check for zero magnitude
-                if a_mag == 0.0 || b_mag == 0.0 {
-                    Ok(1.0) // Maximum distance for zero vectors
-                } else {
-                    Ok(1.0 - (dot / (a_mag * b_mag)))
+                let magnitudes = (T::dot_product(a.raw_data(), a.raw_data()) * T::dot_product(b.raw_data(), b.raw_data())).sqrt();
+                if magnitudes == 0.0 {
+                    return Ok(1.0);
                 }
+                Ok(1.0 - T::dot_product(a.raw_data(), b.raw_data()) / magnitudes)
+            }
+            _ => {
+                let a: Vec<f32> = a.data().iter().map(|x| x.to_f64() as f32).collect();
+                let b: Vec<f32> = b.data().iter().map(|x| x.to_f64() as f32).collect();
+                self.compute_slices(&a, &b)
             }
-            DistanceMetric::DotProduct => Ok(dot_product(a.data(), b.data())),
         }
     }
+
+    /// Raw cosine similarity in [-1, 1]. Returns 0.0 when either vector has zero magnitude.
+    pub fn cosine_similarity(a: &Vector, b: &Vector) -> Result<f32, ZyphyrError> {
+        if a.dim() != b.dim() {
+            return Err(ZyphyrError::InvalidDimension {
+                expected: a.dim(),
+                got: b.dim(),
+            });
+        }
+        Ok(cosine_similarity(a.data(), b.data()))
+    }
+
+    /// Compute the distance between two plain f32 slices of equal length
+    pub fn compute_slices(&self, a: &[f32], b: &[f32]) -> Result<f32, ZyphyrError> {
+        if a.len() != b.len() {
+            return Err(ZyphyrError::InvalidDimension {
+                expected: a.len(),
+                got: b.len(),
+            });
+        }
+        match self {
+            DistanceMetric::Euclidean => Ok(euclidean_distance_padded(a, b)),
+            DistanceMetric::Cosine => Ok(cosine_distance_padded(a, b)),
+            DistanceMetric::DotProduct => Ok(dot_product_padded(a, b)),
+            DistanceMetric::WeightedEuclidean { weights } => weighted_euclidean_distance(a, b, weights),
+            DistanceMetric::JensenShannon => jensen_shannon_divergence(a, b),
+            DistanceMetric::Correlation => Ok(correlation_distance(a, b)),
+            DistanceMetric::Angular => Ok(angle(cosine_similarity(a, b))),
+            DistanceMetric::Tanimoto => Ok(tanimoto_distance(a, b)),
+            DistanceMetric::Custom(distance) => Ok(distance(a, b)),
+        }
+    }
+}
+
+/// Statically dispatched distance, so hot loops can be monomorphized instead of
+/// matching on [`DistanceMetric`] per call. Slices may carry trailing zero padding.
+pub trait Distance {
+    fn distance(a: &[f32], b: &[f32]) -> f32;
+
+    /// The [`DistanceMetric`] this marker stands for, if any, so generic
+    /// searches can apply the same shortcuts as the enum-dispatched ones
+    fn metric() -> Option<DistanceMetric> {
+        None
+    }
+}
+
+/// Zero-sized [`Distance`] marker for [`DistanceMetric::Euclidean`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Euclidean;
+
+/// Zero-sized [`Distance`] marker for [`DistanceMetric::Cosine`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Cosine;
+
+/// Zero-sized [`Distance`] marker for [`DistanceMetric::DotProduct`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DotProduct;
+
+impl Distance for Euclidean {
+    #[inline]
+    fn distance(a: &[f32], b: &[f32]) -> f32 {
+        euclidean_distance_padded(a, b)
+    }
+
+    fn metric() -> Option<DistanceMetric> {
+        Some(DistanceMetric::Euclidean)
+    }
 }
 
-fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
+impl Distance for Cosine {
+    #[inline]
+    fn distance(a: &[f32], b: &[f32]) -> f32 {
+        cosine_distance_padded(a, b)
+    }
+
+    fn metric() -> Option<DistanceMetric> {
+        Some(DistanceMetric::Cosine)
+    }
+}
+
+impl Distance for DotProduct {
+    #[inline]
+    fn distance(a: &[f32], b: &[f32]) -> f32 {
+        dot_product_padded(a, b)
+    }
+
+    fn metric() -> Option<DistanceMetric> {
+        Some(DistanceMetric::DotProduct)
+    }
+}
+
+/// Euclidean distance over padded slices, dispatching to AVX2 or NEON when available.
+/// The zero padding contributes nothing to the sum, so results match the scalar path.
+pub(crate) fn euclidean_distance_padded(a: &[f32], b: &[f32]) -> f32 {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if std::arch::is_x86_feature_detected!("avx2") {
+            return unsafe { euclidean_distance_avx2(a, b) };
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            return unsafe { euclidean_distance_neon(a, b) };
+        }
+    }
+    euclidean_distance(a, b)
+}
+
+pub(crate) fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
     a.iter()
         .zip(b.iter())
         .map(|(x, y)| (x - y) * (x - y))
@@ -46,6 +270,338 @@ fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
         .sqrt()
 }
 
-fn dot_product(a: &[f32], b: &[f32]) -> f32 {
+fn weighted_euclidean_distance(a: &[f32], b: &[f32], weights: &[f32]) -> Result<f32, ZyphyrError> {
+    if weights.len() != a.len() {
+        return Err(ZyphyrError::InvalidDimension {
+            expected: a.len(),
+            got: weights.len(),
+        });
+    }
+    Ok(a.iter()
+        .zip(b.iter())
+        .zip(weights.iter())
+        .map(|((x, y), w)| w * (x - y) * (x - y))
+        .sum::<f32>()
+        .sqrt())
+}
+
+fn check_weights(weights: &[f32]) -> Result<(), ZyphyrError> {
+    match weights.iter().position(|w| !w.is_finite() || *w < 0.0) {
+        Some(i) => Err(ZyphyrError::InvalidData(format!(
+            "weight {} at index {} must be finite and non-negative",
+            weights[i], i
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn jensen_shannon_divergence(a: &[f32], b: &[f32]) -> Result<f32, ZyphyrError> {
+    let a_sum = probability_mass(a)?;
+    let b_sum = probability_mass(b)?;
+    
+    // JS = (KL(p || m) + KL(q || m)) / 2 with m = (p + q) / 2; zero terms contribute nothing
+    let mut divergence = 0.0f64;
+    for (&x, &y) in a.iter().zip(b.iter()) {
+        let p = x as f64 / a_sum;
+        let q = y as f64 / b_sum;
+        let m = (p + q) / 2.0;
+        if p > 0.0 {
+            divergence += p * (p / m).log2();
+        }
+        if q > 0.0 {
+            divergence += q * (q / m).log2();
+        }
+    }
+    Ok((divergence / 2.0).clamp(0.0, 1.0) as f32)
+}
+
+// Total of a probability vector, rejecting negative entries and all-zero input
+fn probability_mass(v: &[f32]) -> Result<f64, ZyphyrError> {
+    if v.iter().any(|&x| x < 0.0) {
+        return Err(ZyphyrError::InvalidData("Jensen-Shannon requires non-negative values".to_string()));
+    }
+    let sum: f64 = v.iter().map(|&x| x as f64).sum();
+    if sum <= 0.0 {
+        return Err(ZyphyrError::InvalidData("Jensen-Shannon requires a non-zero distribution".to_string()));
+    }
+    Ok(sum)
+}
+
+// Cosine distance between the mean-centered vectors. A constant vector has no
+// variance, so its correlation is undefined and it is placed at distance 1.
+fn correlation_distance(a: &[f32], b: &[f32]) -> f32 {
+    let n = a.len() as f32;
+    let a_mean = a.iter().sum::<f32>() / n;
+    let b_mean = b.iter().sum::<f32>() / n;
+    
+    let (mut covariance, mut a_var, mut b_var) = (0.0f32, 0.0f32, 0.0f32);
+    for (&x, &y) in a.iter().zip(b.iter()) {
+        let dx = x - a_mean;
+        let dy = y - b_mean;
+        covariance += dx * dy;
+        a_var += dx * dx;
+        b_var += dy * dy;
+    }
+    if a_var == 0.0 || b_var == 0.0 {
+        return 1.0;
+    }
+    1.0 - covariance / (a_var.sqrt() * b_var.sqrt())
+}
+
+// Clamp before acos: rounding can push a similarity just outside [-1, 1], giving NaN
+fn angle(similarity: f32) -> f32 {
+    similarity.clamp(-1.0, 1.0).acos()
+}
+
+fn cosine_distance(a: &[f32], b: &[f32]) -> f32 {
+    // Zero vectors have similarity 0, which maps to the maximum distance of 1
+    1.0 - cosine_similarity(a, b)
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    cosine_similarity_with_norms(a, b, l2_norm(a), l2_norm(b))
+}
+
+/// Cosine distance from one fused pass accumulating the dot product and both
+/// squared magnitudes, using AVX2/FMA when available
+pub(crate) fn cosine_distance_padded(a: &[f32], b: &[f32]) -> f32 {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if std::arch::is_x86_feature_detected!("avx2") && std::arch::is_x86_feature_detected!("fma") {
+            let (dot, a_sq, b_sq) = unsafe { cosine_parts_avx2(a, b) };
+            return 1.0 - similarity_from_parts(dot, a_sq.sqrt(), b_sq.sqrt());
+        }
+    }
+    cosine_distance(a, b)
+}
+
+// 1 - a·b / (|a|² + |b|² - a·b), taking all three sums from one fused pass
+fn tanimoto_distance(a: &[f32], b: &[f32]) -> f32 {
+    let (dot, a_sq, b_sq) = dot_and_squares(a, b);
+    let denominator = a_sq + b_sq - dot;
+    // Only two all-zero vectors make the denominator vanish
+    if denominator <= 0.0 {
+        return 1.0;
+    }
+    1.0 - dot / denominator
+}
+
+// (a·b, a·a, b·b), using the AVX2 fused kernel when available
+fn dot_and_squares(a: &[f32], b: &[f32]) -> (f32, f32, f32) {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if std::arch::is_x86_feature_detected!("avx2") && std::arch::is_x86_feature_detected!("fma") {
+            return unsafe { cosine_parts_avx2(a, b) };
+        }
+    }
+    a.iter().zip(b).fold((0.0, 0.0, 0.0), |(dot, a_sq, b_sq), (&x, &y)| {
+        (dot + x * y, a_sq + x * x, b_sq + y * y)
+    })
+}
+
+// Similarity from a dot product and magnitudes; zero vectors have similarity 0
+fn similarity_from_parts(dot: f32, a_mag: f32, b_mag: f32) -> f32 {
+    if a_mag == 0.0 || b_mag == 0.0 {
+        return 0.0;
+    }
+    dot / (a_mag * b_mag)
+}
+
+// Cosine similarity given precomputed magnitudes, so cached norms can skip two passes
+fn cosine_similarity_with_norms(a: &[f32], b: &[f32], a_mag: f32, b_mag: f32) -> f32 {
+    // Check for zero magnitude
+    if a_mag == 0.0 || b_mag == 0.0 {
+        return 0.0;
+    }
+    dot_product_padded(a, b) / (a_mag * b_mag)
+}
+
+/// L2 magnitude of a slice
+pub(crate) fn l2_norm(a: &[f32]) -> f32 {
+    a.iter().map(|x| x * x).sum::<f32>().sqrt()
+}
+
+/// Dot product dispatching to AVX2/FMA or NEON when available, with a scalar fallback
+pub(crate) fn dot_product_padded(a: &[f32], b: &[f32]) -> f32 {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if std::arch::is_x86_feature_detected!("avx2") && std::arch::is_x86_feature_detected!("fma") {
+            return unsafe { dot_product_avx2(a, b) };
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            return unsafe { dot_product_neon(a, b) };
+        }
+    }
+    dot_product(a, b)
+}
+
+pub(crate) fn dot_product(a: &[f32], b: &[f32]) -> f32 {
     a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
 }
+
+/// AVX2 Euclidean distance processing 8 f32 lanes per iteration.
+///
+/// # Safety
+/// The caller must ensure the CPU supports AVX2 and that `a` and `b` have equal length.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn euclidean_distance_avx2(a: &[f32], b: &[f32]) -> f32 {
+    use std::arch::x86_64::*;
+
+    let len = a.len().min(b.len());
+    let chunks = len / 8;
+    let mut acc = _mm256_setzero_ps();
+
+    for i in 0..chunks {
+        // Unaligned loads are as fast as aligned ones on aligned data, and stay
+        // correct for slices that don't start on a 32-byte boundary
+        let va = unsafe { _mm256_loadu_ps(a.as_ptr().add(i * 8)) };
+        let vb = unsafe { _mm256_loadu_ps(b.as_ptr().add(i * 8)) };
+        let diff = _mm256_sub_ps(va, vb);
+        acc = _mm256_add_ps(acc, _mm256_mul_ps(diff, diff));
+    }
+
+    // Horizontal sum of the 8 accumulator lanes
+    let mut lanes = [0.0f32; 8];
+    unsafe { _mm256_storeu_ps(lanes.as_mut_ptr(), acc) };
+    let mut sum: f32 = lanes.iter().sum();
+
+    // Handle any tail that isn't a multiple of 8 (e.g. SSE-only padding)
+    for i in chunks * 8..len {
+        let diff = a[i] - b[i];
+        sum += diff * diff;
+    }
+
+    sum.sqrt()
+}
+
+/// AVX2 dot product using fused multiply-add over 8 f32 lanes per iteration.
+///
+/// # Safety
+/// The caller must ensure the CPU supports AVX2 and FMA and that `a` and `b` have equal length.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2,fma")]
+pub(crate) unsafe fn dot_product_avx2(a: &[f32], b: &[f32]) -> f32 {
+    use std::arch::x86_64::*;
+
+    let len = a.len().min(b.len());
+    let chunks = len / 8;
+    let mut acc = _mm256_setzero_ps();
+
+    for i in 0..chunks {
+        let va = unsafe { _mm256_loadu_ps(a.as_ptr().add(i * 8)) };
+        let vb = unsafe { _mm256_loadu_ps(b.as_ptr().add(i * 8)) };
+        acc = _mm256_fmadd_ps(va, vb, acc);
+    }
+
+    let mut lanes = [0.0f32; 8];
+    unsafe { _mm256_storeu_ps(lanes.as_mut_ptr(), acc) };
+    let mut sum: f32 = lanes.iter().sum();
+
+    for i in chunks * 8..len {
+        sum += a[i] * b[i];
+    }
+
+    sum
+}
+
+/// AVX2 fused pass returning `(a·b, a·a, b·b)` over 8 f32 lanes per iteration.
+///
+/// # Safety
+/// The caller must ensure the CPU supports AVX2 and FMA and that `a` and `b` have equal length.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2,fma")]
+pub(crate) unsafe fn cosine_parts_avx2(a: &[f32], b: &[f32]) -> (f32, f32, f32) {
+    use std::arch::x86_64::*;
+
+    let len = a.len().min(b.len());
+    let chunks = len / 8;
+    let mut dot = _mm256_setzero_ps();
+    let mut a_sq = _mm256_setzero_ps();
+    let mut b_sq = _mm256_setzero_ps();
+
+    for i in 0..chunks {
+        let va = unsafe { _mm256_loadu_ps(a.as_ptr().add(i * 8)) };
+        let vb = unsafe { _mm256_loadu_ps(b.as_ptr().add(i * 8)) };
+        dot = _mm256_fmadd_ps(va, vb, dot);
+        a_sq = _mm256_fmadd_ps(va, va, a_sq);
+        b_sq = _mm256_fmadd_ps(vb, vb, b_sq);
+    }
+
+    let mut lanes = [[0.0f32; 8]; 3];
+    unsafe {
+        _mm256_storeu_ps(lanes[0].as_mut_ptr(), dot);
+        _mm256_storeu_ps(lanes[1].as_mut_ptr(), a_sq);
+        _mm256_storeu_ps(lanes[2].as_mut_ptr(), b_sq);
+    }
+    let [mut dot, mut a_sq, mut b_sq] = lanes.map(|l| l.iter().sum::<f32>());
+
+    for i in chunks * 8..len {
+        dot += a[i] * b[i];
+        a_sq += a[i] * a[i];
+        b_sq += b[i] * b[i];
+    }
+
+    (dot, a_sq, b_sq)
+}
+
+/// NEON Euclidean distance processing 4 f32 lanes per iteration.
+///
+/// # Safety
+/// The caller must ensure the CPU supports NEON and that `a` and `b` have equal length.
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn euclidean_distance_neon(a: &[f32], b: &[f32]) -> f32 {
+    use std::arch::aarch64::*;
+
+    let len = a.len().min(b.len());
+    let chunks = len / 4;
+    let mut acc = vdupq_n_f32(0.0);
+
+    for i in 0..chunks {
+        let va = unsafe { vld1q_f32(a.as_ptr().add(i * 4)) };
+        let vb = unsafe { vld1q_f32(b.as_ptr().add(i * 4)) };
+        let diff = vsubq_f32(va, vb);
+        acc = vfmaq_f32(acc, diff, diff);
+    }
+
+    let mut sum = vaddvq_f32(acc);
+
+    for i in chunks * 4..len {
+        let diff = a[i] - b[i];
+        sum += diff * diff;
+    }
+
+    sum.sqrt()
+}
+
+/// NEON dot product processing 4 f32 lanes per iteration.
+///
+/// # Safety
+/// The caller must ensure the CPU supports NEON and that `a` and `b` have equal length.
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn dot_product_neon(a: &[f32], b: &[f32]) -> f32 {
+    use std::arch::aarch64::*;
+
+    let len = a.len().min(b.len());
+    let chunks = len / 4;
+    let mut acc = vdupq_n_f32(0.0);
+
+    for i in 0..chunks {
+        let va = unsafe { vld1q_f32(a.as_ptr().add(i * 4)) };
+        let vb = unsafe { vld1q_f32(b.as_ptr().add(i * 4)) };
+        acc = vfmaq_f32(acc, va, vb);
+    }
+
+    let mut sum = vaddvq_f32(acc);
+
+    for i in chunks * 4..len {
+        sum += a[i] * b[i];
+    }
+
+    sum
+}
@@ -0,0 +1,57 @@
+use crate::vector::distance::{dot_product_padded, euclidean_distance_padded};
+use std::fmt::Debug;
+
+/// Numeric element type that a [`crate::Vector`] can store.
+///
+/// The distance kernels default to a scalar loop accumulating in `f64`;
+/// `f32` overrides them with the SIMD-dispatched implementations.
+pub trait VectorElement: Copy + Default + PartialOrd + Debug + Send + Sync + 'static {
+    fn to_f64(self) -> f64;
+
+    fn is_finite(self) -> bool;
+
+    /// Euclidean distance between equal-length slices
+    fn euclidean_distance(a: &[Self], b: &[Self]) -> f32 {
+        a.iter()
+            .zip(b)
+            .map(|(&x, &y)| {
+                let d = x.to_f64() - y.to_f64();
+                d * d
+            })
+            .sum::<f64>()
+            .sqrt() as f32
+    }
+
+    /// Dot product of equal-length slices
+    fn dot_product(a: &[Self], b: &[Self]) -> f32 {
+        a.iter().zip(b).map(|(&x, &y)| x.to_f64() * y.to_f64()).sum::<f64>() as f32
+    }
+}
+
+impl VectorElement for f32 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn is_finite(self) -> bool {
+        f32::is_finite(self)
+    }
+
+    fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
+        euclidean_distance_padded(a, b)
+    }
+
+    fn dot_product(a: &[f32], b: &[f32]) -> f32 {
+        dot_product_padded(a, b)
+    }
+}
+
+impl VectorElement for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn is_finite(self) -> bool {
+        f64::is_finite(self)
+    }
+}
@@ -0,0 +1,69 @@
+use crate::{DistanceMetric, ZyphyrError};
+use crate::vector::vector_aligned::check_finite;
+use half::f16;
+use half::slice::HalfFloatSliceExt;
+use std::mem;
+
+/// Vector stored in IEEE 754 half precision, halving memory relative to f32.
+/// Distances are computed after widening back to f32.
+#[derive(Debug, Clone)]
+pub struct HalfVector {
+    id: String,
+    data: Vec<f16>,
+}
+
+impl HalfVector {
+    /// Narrow `data` to half precision. Like [`crate::Vector::new`], rejects empty
+    /// and non-finite data, and also values beyond the f16 range (about ±65504)
+    /// that would round to infinity.
+    pub fn from_f32_slice(id: impl Into<String>, data: &[f32]) -> Result<Self, ZyphyrError> {
+        check_finite(data)?;
+        if data.is_empty() {
+            return Err(ZyphyrError::InvalidDimension { expected: 1, got: 0 });
+        }
+        
+        let mut half_data = vec![f16::ZERO; data.len()];
+        half_data.convert_from_f32_slice(data);
+        if let Some(index) = half_data.iter().position(|x| x.is_infinite()) {
+            return Err(ZyphyrError::InvalidData(format!(
+                "value {} at index {} is out of f16 range",
+                data[index], index
+            )));
+        }
+        
+        Ok(HalfVector {
+            id: id.into(),
+            data: half_data,
+        })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn data(&self) -> &[f16] {
+        &self.data
+    }
+
+    pub fn dim(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Widen the stored values back to f32
+    pub fn to_f32(&self) -> Vec<f32> {
+        let mut widened = vec![0.0f32; self.data.len()];
+        self.data.convert_to_f32_slice(&mut widened);
+        widened
+    }
+
+    /// Distance to `other` under `metric`, computed in f32
+    pub fn distance_to(&self, other: &HalfVector, metric: DistanceMetric) -> Result<f32, ZyphyrError> {
+        metric.compute_slices(&self.to_f32(), &other.to_f32())
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        mem::size_of::<Self>() +
+        self.id.capacity() +
+        self.data.capacity() * mem::size_of::<f16>()
+    }
+}
@@ -1,6 +1,23 @@
-pub use self::collection::VectorCollection;
-pub use self::distance::DistanceMetric;
-pub use self::vector::Vector;
-mod vector;
+pub use self::collection::{VectorCollection, VectorCollectionBuilder, SearchOptions, SearchScratch, TieBreak, CollectionStats};
+pub use self::collection::merge_search_results;
+pub use self::concurrent::ConcurrentCollection;
+pub use self::sharded::ShardedCollection;
+pub use self::distance::{DistanceMetric, CustomDistanceFn, Distance, Euclidean, Cosine, DotProduct};
+pub use self::vector_aligned::{Vector, Metadata};
+pub use self::element::VectorElement;
+pub use self::quantized::{QuantizedVector, BinaryVector, quantized_euclidean, hamming_distance};
+pub use self::sparse::{SparseVector, SparseCollection};
+pub use self::pq::{ProductQuantizer, PqCode, DistanceTable};
+#[cfg(feature = "f16")]
+pub use self::half::HalfVector;
+mod vector_aligned;
+mod element;
 mod collection;
-mod distance;
\ No newline at end of file
+mod concurrent;
+mod sharded;
+pub(crate) mod distance;
+mod quantized;
+mod sparse;
+mod pq;
+#[cfg(feature = "f16")]
+mod half;
\ No newline at end of file
@@ -0,0 +1,206 @@
+use crate::{Vector, ZyphyrError, DistanceMetric};
+use crate::index::kmeans::{kmeans, nearest_centroid};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::cmp::Ordering;
+use std::mem;
+
+/// Number of Lloyd iterations used when training each subspace codebook
+const TRAIN_ITERATIONS: usize = 25;
+
+/// Product-quantized encoding of a vector: one centroid index per subspace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PqCode {
+    id: String,
+    codes: Vec<u8>,
+}
+
+impl PqCode {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn codes(&self) -> &[u8] {
+        &self.codes
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        mem::size_of::<Self>() + self.id.capacity() + self.codes.capacity()
+    }
+}
+
+/// Product quantizer: each vector is split into `m` contiguous subvectors and each
+/// subvector is replaced by the nearest of `2^nbits` centroids trained for its subspace
+#[derive(Debug, Clone)]
+pub struct ProductQuantizer {
+    codebooks: Vec<Vec<Vec<f32>>>,  // [subspace][centroid][component]
+    dim: usize,
+    sub_dim: usize,
+}
+
+impl ProductQuantizer {
+    /// Train `m` codebooks of `2^nbits` centroids with k-means. The vector dimension
+    /// must be divisible by `m`, `nbits` must be in 1..=8, and there must be at
+    /// least `2^nbits` training vectors. `seed` drives the k-means++ initialization,
+    /// so the same seed and input always give the same codebooks.
+    pub fn train(vectors: &[Vector], m: usize, nbits: usize, seed: u64) -> Result<Self, ZyphyrError> {
+        if nbits == 0 || nbits > 8 {
+            return Err(ZyphyrError::Other(format!("PQ nbits must be in 1..=8, got {}", nbits)));
+        }
+        let dim = match vectors.first() {
+            Some(v) => v.dim(),
+            None => return Err(ZyphyrError::Other("Cannot train PQ on an empty set".to_string())),
+        };
+        if m == 0 || dim % m != 0 {
+            return Err(ZyphyrError::Other(format!(
+                "PQ subspace count {} must evenly divide dimension {}",
+                m, dim
+            )));
+        }
+        for v in vectors {
+            if v.dim() != dim {
+                return Err(ZyphyrError::InvalidDimension { expected: dim, got: v.dim() });
+            }
+        }
+        
+        let sub_dim = dim / m;
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut codebooks = Vec::with_capacity(m);
+        for sub in 0..m {
+            let points: Vec<&[f32]> = vectors
+                .iter()
+                .map(|v| &v.data()[sub * sub_dim..(sub + 1) * sub_dim])
+                .collect();
+            let clustering = kmeans(&points, 1 << nbits, TRAIN_ITERATIONS, &DistanceMetric::Euclidean, &mut rng)?;
+            codebooks.push(clustering.centroids);
+        }
+        
+        Ok(ProductQuantizer { codebooks, dim, sub_dim })
+    }
+
+    /// Encode `vector` as the nearest centroid index in each subspace
+    pub fn encode(&self, vector: &Vector) -> Result<PqCode, ZyphyrError> {
+        self.check_dim(vector)?;
+        let codes = self.codebooks
+            .iter()
+            .zip(vector.data().chunks(self.sub_dim))
+            .map(|(codebook, sub)| {
+                nearest_centroid(sub, codebook, &DistanceMetric::Euclidean).map(|(index, _)| index as u8)
+            })
+            .collect::<Result<Vec<u8>, ZyphyrError>>()?;
+        Ok(PqCode { id: vector.id().to_string(), codes })
+    }
+
+    /// Reconstruct the approximate vector a code stands for. Fails if the code
+    /// wasn't produced by a quantizer with the same `m` and `nbits`.
+    pub fn decode(&self, code: &PqCode) -> Result<Vec<f32>, ZyphyrError> {
+        self.check_code(code)?;
+        Ok(code.codes
+            .iter()
+            .zip(&self.codebooks)
+            .flat_map(|(&c, codebook)| codebook[c as usize].iter().copied())
+            .collect())
+    }
+
+    /// Precompute the squared distance from each query subvector to every centroid,
+    /// so distances to any number of codes cost `m` lookups each
+    pub fn distance_table(&self, query: &Vector) -> Result<DistanceTable, ZyphyrError> {
+        self.check_dim(query)?;
+        let table = self.codebooks
+            .iter()
+            .zip(query.data().chunks(self.sub_dim))
+            .map(|(codebook, sub)| {
+                codebook
+                    .iter()
+                    .map(|centroid| sub.iter().zip(centroid).map(|(a, b)| (a - b) * (a - b)).sum())
+                    .collect()
+            })
+            .collect();
+        Ok(DistanceTable { table })
+    }
+
+    /// Approximate top-`k` codes by asymmetric Euclidean distance to the raw `query`
+    pub fn search(
+        &self,
+        query: &Vector,
+        codes: &[PqCode],
+        k: usize,
+    ) -> Result<Vec<(String, f32)>, ZyphyrError> {
+        let table = self.distance_table(query)?;
+        for code in codes {
+            self.check_code(code)?;
+        }
+        let mut results: Vec<(String, f32)> = codes
+            .iter()
+            .map(|code| (code.id.clone(), table.distance(code)))
+            .collect();
+        results.sort_by(|a, b| {
+            a.1.partial_cmp(&b.1)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        results.truncate(k);
+        Ok(results)
+    }
+
+    /// Number of subspaces, which is also the code length in bytes
+    pub fn m(&self) -> usize {
+        self.codebooks.len()
+    }
+
+    /// Centroids per subspace
+    pub fn ksub(&self) -> usize {
+        self.codebooks.first().map_or(0, Vec::len)
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Trained centroids, indexed `[subspace][centroid][component]`
+    pub(crate) fn codebooks(&self) -> &[Vec<Vec<f32>>] {
+        &self.codebooks
+    }
+
+    fn check_dim(&self, vector: &Vector) -> Result<(), ZyphyrError> {
+        if vector.dim() != self.dim {
+            return Err(ZyphyrError::InvalidDimension { expected: self.dim, got: vector.dim() });
+        }
+        Ok(())
+    }
+
+    // Codes from a quantizer with another `m` or `nbits` would index past the codebooks
+    fn check_code(&self, code: &PqCode) -> Result<(), ZyphyrError> {
+        if code.codes.len() != self.m() {
+            return Err(ZyphyrError::InvalidDimension { expected: self.m(), got: code.codes.len() });
+        }
+        if let Some(&c) = code.codes.iter().find(|&&c| c as usize >= self.ksub()) {
+            return Err(ZyphyrError::InvalidData(format!(
+                "PQ code {} is out of range for {} centroids per subspace",
+                c,
+                self.ksub()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Per-query lookup table for asymmetric distance computation (ADC)
+#[derive(Debug, Clone)]
+pub struct DistanceTable {
+    table: Vec<Vec<f32>>,  // [subspace][centroid] squared distances
+}
+
+impl DistanceTable {
+    /// Approximate Euclidean distance from the query to the vector behind `code`.
+    /// The code must come from the quantizer that built this table; a code with
+    /// out-of-range centroid indices panics.
+    pub fn distance(&self, code: &PqCode) -> f32 {
+        code.codes
+            .iter()
+            .zip(&self.table)
+            .map(|(&c, row)| row[c as usize])
+            .sum::<f32>()
+            .sqrt()
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{Vector, ZyphyrError};
+use std::mem;
+
+/// Scalar-quantized vector storing one i8 code per dimension.
+/// Values are reconstructed as `code * scale + offset`.
+#[derive(Debug, Clone)]
+pub struct QuantizedVector {
+    id: String,
+    codes: Vec<i8>,
+    scale: f32,   // Width of one quantization step
+    offset: f32,  // Value represented by code 0 (midpoint of the data range)
+}
+
+impl QuantizedVector {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn codes(&self) -> &[i8] {
+        &self.codes
+    }
+
+    pub fn dim(&self) -> usize {
+        self.codes.len()
+    }
+
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    pub fn offset(&self) -> f32 {
+        self.offset
+    }
+
+    /// Reconstruct approximate f32 values from the codes
+    pub fn dequantize(&self) -> Vec<f32> {
+        self.codes.iter().map(|&c| self.decode(c)).collect()
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        mem::size_of::<Self>() +
+        self.id.capacity() +
+        self.codes.capacity() * mem::size_of::<i8>()
+    }
+
+    #[inline]
+    fn decode(&self, code: i8) -> f32 {
+        code as f32 * self.scale + self.offset
+    }
+}
+
+/// Binary-quantized vector packing one sign bit per dimension into u64 words
+#[derive(Debug, Clone)]
+pub struct BinaryVector {
+    id: String,
+    bits: Vec<u64>,
+    dim: usize,
+}
+
+impl BinaryVector {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn bits(&self) -> &[u64] {
+        &self.bits
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        mem::size_of::<Self>() +
+        self.id.capacity() +
+        self.bits.capacity() * mem::size_of::<u64>()
+    }
+
+    // Mask selecting the valid bits of the final word
+    fn tail_mask(&self) -> u64 {
+        match self.dim % 64 {
+            0 => u64::MAX,
+            used => (1u64 << used) - 1,
+        }
+    }
+}
+
+impl Vector {
+    /// Quantize to i8 by mapping this vector's [min, max] range linearly onto [-127, 127]
+    pub fn quantize_scalar(&self) -> QuantizedVector {
+        let data = self.data();
+        let (min, max) = data.iter().fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), &x| {
+            (lo.min(x), hi.max(x))
+        });
+        
+        let offset = (min + max) / 2.0;
+        let scale = (max - min) / 254.0;
+        
+        let codes = data
+            .iter()
+            .map(|&x| {
+                if scale > 0.0 {
+                    ((x - offset) / scale).round().clamp(-127.0, 127.0) as i8
+                } else {
+                    // Constant vector: every value equals the offset
+                    0
+                }
+            })
+            .collect();
+        
+        QuantizedVector {
+            id: self.id().to_string(),
+            codes,
+            scale,
+            offset,
+        }
+    }
+
+    /// Pack the sign of each real dimension into a bitset (1 for positive values).
+    /// Padding dimensions are never encoded.
+    pub fn quantize_binary(&self) -> BinaryVector {
+        let data = self.data();
+        let mut bits = vec![0u64; data.len().div_ceil(64)];
+        for (i, &x) in data.iter().enumerate() {
+            if x > 0.0 {
+                bits[i / 64] |= 1u64 << (i % 64);
+            }
+        }
+        
+        BinaryVector {
+            id: self.id().to_string(),
+            bits,
+            dim: data.len(),
+        }
+    }
+}
+
+/// Number of dimensions whose sign differs between two binary vectors.
+/// Returns `ZyphyrError::InvalidDimension` if their dimensions differ.
+pub fn hamming_distance(a: &BinaryVector, b: &BinaryVector) -> Result<u32, ZyphyrError> {
+    if a.dim() != b.dim() {
+        return Err(ZyphyrError::InvalidDimension { expected: a.dim(), got: b.dim() });
+    }
+    let words = a.bits.len();
+    if words == 0 {
+        return Ok(0);
+    }
+    
+    let full: u32 = a.bits[..words - 1]
+        .iter()
+        .zip(b.bits[..words - 1].iter())
+        .map(|(x, y)| (x ^ y).count_ones())
+        .sum();
+    // Mask the last word so bits beyond `dim` can never contribute
+    let last = (a.bits[words - 1] ^ b.bits[words - 1]) & a.tail_mask();
+    Ok(full + last.count_ones())
+}
+
+/// Approximate Euclidean distance between two scalar-quantized vectors.
+/// Returns `ZyphyrError::InvalidDimension` if their dimensions differ.
+pub fn quantized_euclidean(a: &QuantizedVector, b: &QuantizedVector) -> Result<f32, ZyphyrError> {
+    if a.dim() != b.dim() {
+        return Err(ZyphyrError::InvalidDimension { expected: a.dim(), got: b.dim() });
+    }
+    Ok(a.codes
+        .iter()
+        .zip(b.codes.iter())
+        .map(|(&x, &y)| {
+            let diff = a.decode(x) - b.decode(y);
+            diff * diff
+        })
+        .sum::<f32>()
+        .sqrt())
+}
@@ -0,0 +1,110 @@
+use crate::{Vector, VectorCollection, ZyphyrError, DistanceMetric};
+use crate::vector::collection::merge_search_results;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Vectors partitioned across several [`VectorCollection`] shards by a hash of
+/// their id. Each id always routes to the same shard, so lookups and removes
+/// touch one shard. Searches query every shard (in parallel with the `rayon`
+/// feature) and merge the per-shard top-k lists.
+pub struct ShardedCollection {
+    shards: Vec<VectorCollection>,
+}
+
+impl ShardedCollection {
+    /// Create `num_shards` empty shards. Fails if `num_shards` is zero.
+    pub fn new(num_shards: usize) -> Result<Self, ZyphyrError> {
+        if num_shards == 0 {
+            return Err(ZyphyrError::Other("a sharded collection needs at least one shard".to_string()));
+        }
+        Ok(ShardedCollection {
+            shards: (0..num_shards).map(|_| VectorCollection::new()).collect(),
+        })
+    }
+
+    /// Insert into the shard owning the vector's id. Every shard must share one
+    /// dimension, and ids are unique across the whole collection.
+    pub fn insert(&mut self, vector: Vector) -> Result<(), ZyphyrError> {
+        if let Some(dims) = self.dimensions()
+            && vector.dim() != dims
+        {
+            return Err(ZyphyrError::InvalidDimension { expected: dims, got: vector.dim() });
+        }
+        let shard = self.shard_for(vector.id());
+        self.shards[shard].insert(vector)
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Vector> {
+        self.shards[self.shard_for(id)].get(id)
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.shards[self.shard_for(id)].contains(id)
+    }
+
+    pub fn remove(&mut self, id: &str) -> Option<Vector> {
+        let shard = self.shard_for(id);
+        self.shards[shard].remove(id)
+    }
+
+    /// The `k` nearest vectors across all shards, identical to searching one
+    /// collection holding every vector
+    pub fn search(
+        &self,
+        query: &Vector,
+        k: usize,
+        metric: DistanceMetric,
+    ) -> Result<Vec<(String, f32)>, ZyphyrError> {
+        if let Some(dims) = self.dimensions()
+            && query.dim() != dims
+        {
+            return Err(ZyphyrError::InvalidDimension { expected: dims, got: query.dim() });
+        }
+        
+        #[cfg(feature = "rayon")]
+        let per_shard = {
+            use rayon::prelude::*;
+            self.shards
+                .par_iter()
+                .map(|shard| shard.search(query, k, metric.clone()))
+                .collect::<Result<Vec<_>, _>>()?
+        };
+        #[cfg(not(feature = "rayon"))]
+        let per_shard = self.shards
+            .iter()
+            .map(|shard| shard.search(query, k, metric.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        Ok(merge_search_results(&per_shard, k))
+    }
+
+    /// Total number of vectors across all shards
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(VectorCollection::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(VectorCollection::is_empty)
+    }
+
+    pub fn num_shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// The shards themselves, e.g. to inspect how evenly ids are spread
+    pub fn shards(&self) -> &[VectorCollection] {
+        &self.shards
+    }
+
+    /// The shared dimension, or `None` while every shard is empty
+    pub fn dimensions(&self) -> Option<usize> {
+        self.shards.iter().find_map(VectorCollection::dimensions)
+    }
+
+    // DefaultHasher::new() uses fixed keys, so routing is stable for a given build
+    fn shard_for(&self, id: &str) -> usize {
+        let mut hasher = DefaultHasher::new();
+        id.hash(&mut hasher);
+        (hasher.finish() % self.shards.len() as u64) as usize
+    }
+}
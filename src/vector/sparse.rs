@@ -0,0 +1,213 @@
+use crate::{Vector, ZyphyrError};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::mem;
+
+/// Sparse vector storing only its non-zero entries as parallel index/value lists.
+/// `indices` are strictly increasing and all less than `dim`.
+#[derive(Debug, Clone)]
+pub struct SparseVector {
+    id: String,
+    indices: Vec<u32>,
+    values: Vec<f32>,
+    dim: usize,
+}
+
+impl SparseVector {
+    pub fn new(
+        id: impl Into<String>,
+        indices: Vec<u32>,
+        values: Vec<f32>,
+        dim: usize,
+    ) -> Result<Self, ZyphyrError> {
+        if indices.len() != values.len() {
+            return Err(ZyphyrError::Other(format!(
+                "Sparse vector has {} indices but {} values",
+                indices.len(),
+                values.len()
+            )));
+        }
+        if indices.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(ZyphyrError::Other("Sparse indices must be strictly increasing".to_string()));
+        }
+        if let Some(&last) = indices.last()
+            && last as usize >= dim
+        {
+            return Err(ZyphyrError::Other(format!(
+                "Sparse index {} out of range for dimension {}",
+                last, dim
+            )));
+        }
+        if let Some(value) = values.iter().find(|x| !x.is_finite()) {
+            return Err(ZyphyrError::InvalidData(format!("non-finite value {}", value)));
+        }
+        
+        Ok(SparseVector {
+            id: id.into(),
+            indices,
+            values,
+            dim,
+        })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+
+    pub fn values(&self) -> &[f32] {
+        &self.values
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Number of stored (non-zero) entries
+    pub fn nnz(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn norm(&self) -> f32 {
+        self.values.iter().map(|x| x * x).sum::<f32>().sqrt()
+    }
+
+    /// Dot product, walking both sorted index lists in a single merge pass
+    pub fn dot(&self, other: &SparseVector) -> Result<f32, ZyphyrError> {
+        self.check_dim(other)?;
+        
+        let (mut i, mut j) = (0, 0);
+        let mut sum = 0.0;
+        while i < self.indices.len() && j < other.indices.len() {
+            match self.indices[i].cmp(&other.indices[j]) {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    sum += self.values[i] * other.values[j];
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        Ok(sum)
+    }
+
+    /// Cosine distance (1 - similarity); a zero vector is at the maximum distance of 1
+    pub fn cosine_distance(&self, other: &SparseVector) -> Result<f32, ZyphyrError> {
+        let dot = self.dot(other)?;
+        let magnitude = self.norm() * other.norm();
+        if magnitude == 0.0 {
+            return Ok(1.0);
+        }
+        Ok(1.0 - dot / magnitude)
+    }
+
+    /// Expand into a dense [`Vector`] with the same id
+    pub fn to_dense(&self) -> Result<Vector, ZyphyrError> {
+        let mut data = vec![0.0; self.dim];
+        for (&index, &value) in self.indices.iter().zip(&self.values) {
+            data[index as usize] = value;
+        }
+        Vector::new(self.id.clone(), data)
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        mem::size_of::<Self>() +
+        self.id.capacity() +
+        self.indices.capacity() * mem::size_of::<u32>() +
+        self.values.capacity() * mem::size_of::<f32>()
+    }
+
+    fn check_dim(&self, other: &SparseVector) -> Result<(), ZyphyrError> {
+        if self.dim != other.dim {
+            return Err(ZyphyrError::InvalidDimension { expected: self.dim, got: other.dim });
+        }
+        Ok(())
+    }
+}
+
+/// Collection of sparse vectors searchable by dot-product score through an
+/// inverted index, so a query only touches vectors sharing one of its dimensions
+#[derive(Debug, Default)]
+pub struct SparseCollection {
+    vectors: Vec<SparseVector>,
+    id_to_index: HashMap<String, usize>,
+    postings: HashMap<u32, Vec<(usize, f32)>>,  // Dimension -> (vector index, value)
+    dimensions: Option<usize>,
+}
+
+impl SparseCollection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, vector: SparseVector) -> Result<(), ZyphyrError> {
+        if let Some(dims) = self.dimensions
+            && vector.dim() != dims
+        {
+            return Err(ZyphyrError::InvalidDimension { expected: dims, got: vector.dim() });
+        }
+        if self.id_to_index.contains_key(vector.id()) {
+            return Err(ZyphyrError::Other(format!("Duplicate ID: {}", vector.id())));
+        }
+        self.dimensions = Some(vector.dim());
+        
+        let index = self.vectors.len();
+        for (&dim, &value) in vector.indices().iter().zip(vector.values()) {
+            self.postings.entry(dim).or_default().push((index, value));
+        }
+        self.id_to_index.insert(vector.id().to_string(), index);
+        self.vectors.push(vector);
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> Option<&SparseVector> {
+        self.id_to_index.get(id).map(|&index| &self.vectors[index])
+    }
+
+    /// Top-`k` vectors by descending dot product with `query`, ties broken by id.
+    /// Only vectors sharing at least one non-zero dimension with the query are scored.
+    pub fn search(&self, query: &SparseVector, k: usize) -> Result<Vec<(String, f32)>, ZyphyrError> {
+        if let Some(dims) = self.dimensions
+            && query.dim() != dims
+        {
+            return Err(ZyphyrError::InvalidDimension { expected: dims, got: query.dim() });
+        }
+        
+        let mut scores: HashMap<usize, f32> = HashMap::new();
+        for (dim, &weight) in query.indices().iter().zip(query.values()) {
+            if let Some(postings) = self.postings.get(dim) {
+                for &(index, value) in postings {
+                    *scores.entry(index).or_insert(0.0) += weight * value;
+                }
+            }
+        }
+        
+        let mut results: Vec<(String, f32)> = scores
+            .into_iter()
+            .map(|(index, score)| (self.vectors[index].id().to_string(), score))
+            .collect();
+        results.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        results.truncate(k);
+        Ok(results)
+    }
+
+    pub fn dimensions(&self) -> Option<usize> {
+        self.dimensions
+    }
+
+    pub fn len(&self) -> usize {
+        self.vectors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vectors.is_empty()
+    }
+}
@@ -1,77 +1,124 @@
 use crate::ZyphyrError;
+use crate::vector::element::VectorElement;
 use crate::utils::alignment::{SIMD_ALIGNMENT, is_aligned, pad_dimension, get_simd_width};
+use crate::utils::simd::prefetch_read;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::mem;
-use aligned_vec::AlignedVec;
+use aligned_vec::{AVec, ConstAlign};
 
+/// Heap buffer aligned to `SIMD_ALIGNMENT` bytes
+type AlignedVec<T> = AVec<T, ConstAlign<SIMD_ALIGNMENT>>;
+
+// Bytes of stored vectors scored per `batch_distance` tile, about half a typical L2
+const BATCH_TILE_BYTES: usize = 128 * 1024;
+
+// Batches smaller than this many tiles aren't worth handing to the thread pool
+#[cfg(feature = "rayon")]
+const PARALLEL_MIN_TILES: usize = 4;
+
+/// Application metadata attached to a vector
+pub type Metadata = HashMap<String, String>;
+
+/// A dense vector stored in a zero-padded, SIMD-aligned buffer.
+///
+/// The element type defaults to `f32`, so plain `Vector` means `Vector<f32>`;
+/// that is the type collections and indexes store. Other [`VectorElement`]
+/// types such as `f64` support construction, accessors and
+/// [`crate::DistanceMetric::compute_generic`].
 #[repr(C, align(32))]  // Increased alignment for AVX-512
 #[derive(Debug, Clone)]
-pub struct Vector {
+pub struct Vector<T: VectorElement = f32> {
     id: String,            // Unique identifier
-    data: AlignedVec<f32>, // Properly aligned vector data
+    data: AlignedVec<T>,   // Properly aligned vector data
     dim: usize,            // Original vector dimension
     padded_dim: usize,     // Padded dimension for SIMD operations
     is_normalized: bool,   // Flag for cosine similarity optimization
+    norm: Option<f32>,     // Lazily cached L2 norm of the real dimensions
+    original_norm: Option<f32>, // Magnitude before normalization, for `denormalize`
+    metadata: Option<Metadata>, // Application payload returned with search hits
 }
 
-impl Vector {
-    pub fn new(id: impl Into<String>, data: Vec<f32>) -> Result<Self, ZyphyrError> {
-        let dim = data.len();
-        if dim == 0 {
+impl<T: VectorElement> Vector<T> {
+    /// Build a vector of any [`VectorElement`] type, e.g. `Vector::<f64>::from_elements`.
+    /// Rejects empty data and non-finite values, like [`Vector::new`].
+    pub fn from_elements(id: impl Into<String>, data: Vec<T>) -> Result<Self, ZyphyrError> {
+        check_finite(&data)?;
+        if data.is_empty() {
             return Err(ZyphyrError::InvalidDimension { expected: 1, got: 0 });
         }
-        
-        // Pad to optimize for SIMD operations
-        let simd_width = get_simd_width();
-        let padded_dim = pad_dimension(dim, simd_width);
-        
-        // Create a properly aligned vector
-        let mut aligned_data = AlignedVec::with_capacity(SIMD_ALIGNMENT, padded_dim);
-        aligned_data.extend_from_slice(&data);
-        aligned_data.resize(padded_dim, 0.0); // Pad with zeros
-        
-        Ok(Vector {
-            id: id.into(),
-            data: aligned_data,
-            dim,
-            padded_dim,
-            is_normalized: false,
-        })
+        Self::padded(id.into(), &data)
     }
 
-    pub fn from_slice(id: impl Into<String>, data: &[f32]) -> Result<Self, ZyphyrError> {
+    // Copy already-validated, non-empty data into a zero-padded aligned buffer
+    fn padded(id: String, data: &[T]) -> Result<Self, ZyphyrError> {
         let dim = data.len();
-        if dim == 0 {
-            return Err(ZyphyrError::InvalidDimension { expected: 1, got: 0 });
-        }
         
         // Pad to optimize for SIMD operations
         let simd_width = get_simd_width();
         let padded_dim = pad_dimension(dim, simd_width);
         
         // Create a properly aligned vector
-        let mut aligned_data = AlignedVec::with_capacity(SIMD_ALIGNMENT, padded_dim);
+        let mut aligned_data = aligned_buffer(padded_dim)?;
         aligned_data.extend_from_slice(data);
-        aligned_data.resize(padded_dim, 0.0); // Pad with zeros
+        aligned_data.resize(padded_dim, T::default()); // Pad with zeros
         
         Ok(Vector {
-            id: id.into(),
+            id,
             data: aligned_data,
             dim,
             padded_dim,
             is_normalized: false,
+            norm: None,
+            original_norm: None,
+            metadata: None,
         })
     }
 
+    /// Attach an application metadata payload (e.g. source URL, timestamp)
+    pub fn with_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
     pub fn id(&self) -> &str {
         &self.id
     }
 
-    pub fn data(&self) -> &[f32] {
+    // Collections keep their own id index, so only they may change an id in place
+    pub(crate) fn set_id(&mut self, id: String) {
+        self.id = id;
+    }
+
+    pub fn metadata(&self) -> Option<&Metadata> {
+        self.metadata.as_ref()
+    }
+
+    /// The real (unpadded) values; after [`Vector::normalize`] these are the normalized values
+    pub fn data(&self) -> &[T] {
         // Return only the unpadded portion
         &self.data[..self.dim]
     }
     
-    pub fn raw_data(&self) -> &[f32] {
+    /// Edit the real (unpadded) values in place with `f`, e.g. to apply a delta.
+    /// The padding is out of reach, so it stays zeroed. Edits invalidate the
+    /// normalized flag and the cached norms, so both are reset here.
+    ///
+    /// Fails with `ZyphyrError::InvalidData`, leaving the vector unchanged, if
+    /// the edit produces a non-finite value.
+    pub fn update_data(&mut self, f: impl FnOnce(&mut [T])) -> Result<(), ZyphyrError> {
+        let mut edited = self.data[..self.dim].to_vec();
+        f(&mut edited);
+        check_finite(&edited)?;
+        
+        self.data[..self.dim].copy_from_slice(&edited);
+        self.is_normalized = false;
+        self.norm = None;
+        self.original_norm = None;
+        Ok(())
+    }
+    
+    pub fn raw_data(&self) -> &[T] {
         // Return the full padded data (for internal use)
         &self.data
     }
@@ -84,6 +131,141 @@ impl Vector {
         self.padded_dim
     }
 
+    /// Raw pointer to the padded buffer, e.g. for FFI or GPU upload without a copy.
+    /// It is valid for [`Vector::padded_len`] elements and aligned to
+    /// [`SIMD_ALIGNMENT`] bytes; the elements past [`Vector::dim`] are zero.
+    ///
+    /// # Safety
+    ///
+    /// Obtaining the pointer is safe; dereferencing it is not. The pointer dangles
+    /// once the vector is dropped, and writes through it are only sound while no
+    /// other reference exists. Any mutation of the vector (e.g. [`Vector::normalize`])
+    /// may change the contents, so re-read after mutating.
+    pub fn as_ptr(&self) -> *const T {
+        self.data.as_ptr()
+    }
+
+    /// Number of elements behind [`Vector::as_ptr`], the same as [`Vector::padded_dim`]
+    pub fn padded_len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Whether the real dimensions have been scaled to unit length (or were all zero)
+    pub fn is_normalized(&self) -> bool {
+        self.is_normalized
+    }
+
+    // Ensure memory alignment for SIMD
+    pub fn is_aligned(&self) -> bool {
+        let ptr = self.data.as_ptr() as *const u8;
+        is_aligned(ptr, SIMD_ALIGNMENT)
+    }
+
+    // Add memory usage tracking
+    pub fn memory_usage(&self) -> usize {
+        mem::size_of::<Self>() + 
+        self.id.capacity() +
+        self.padded_dim * mem::size_of::<T>() +
+        self.metadata_memory_usage()
+    }
+
+    // Approximate heap usage of the metadata map: entry slots plus string contents
+    fn metadata_memory_usage(&self) -> usize {
+        match &self.metadata {
+            Some(map) => {
+                map.capacity() * mem::size_of::<(String, String)>() +
+                map.iter().map(|(k, v)| k.capacity() + v.capacity()).sum::<usize>()
+            }
+            None => 0,
+        }
+    }
+}
+
+impl Vector {
+    pub fn new(id: impl Into<String>, data: Vec<f32>) -> Result<Self, ZyphyrError> {
+        Self::from_slice(id, &data)
+    }
+
+    pub fn from_slice(id: impl Into<String>, data: &[f32]) -> Result<Self, ZyphyrError> {
+        check_finite(data)?;
+        Self::non_empty(id.into(), data)
+    }
+
+    /// Like [`Vector::new`], but L2-normalizes the real dimensions while copying
+    /// them into the padded buffer, instead of a separate [`Vector::normalize`]
+    /// pass. A zero vector stays zero and is still flagged as normalized.
+    pub fn new_normalized(id: impl Into<String>, data: Vec<f32>) -> Result<Self, ZyphyrError> {
+        check_finite(&data)?;
+        if data.is_empty() {
+            return Err(ZyphyrError::InvalidDimension { expected: 1, got: 0 });
+        }
+        
+        let magnitude = crate::vector::distance::l2_norm(&data);
+        // Divide (rather than multiply by the reciprocal) to match `normalize` exactly
+        let divisor = if magnitude > 0.0 { magnitude } else { 1.0 };
+        let padded_dim = pad_dimension(data.len(), get_simd_width());
+        let mut aligned_data = aligned_buffer(padded_dim)?;
+        for &x in &data {
+            aligned_data.push(x / divisor);
+        }
+        aligned_data.resize(padded_dim, 0.0);
+        
+        Ok(Vector {
+            id: id.into(),
+            data: aligned_data,
+            dim: data.len(),
+            padded_dim,
+            is_normalized: true,
+            norm: None,
+            original_norm: Some(magnitude),
+            metadata: None,
+        })
+    }
+
+    /// Like [`Vector::new`], but skips the NaN/infinity check for data the caller
+    /// has already validated. Empty data is still rejected.
+    pub fn new_unchecked(id: impl Into<String>, data: Vec<f32>) -> Result<Self, ZyphyrError> {
+        Self::non_empty(id.into(), &data)
+    }
+
+    fn non_empty(id: String, data: &[f32]) -> Result<Self, ZyphyrError> {
+        if data.is_empty() {
+            return Err(ZyphyrError::InvalidDimension { expected: 1, got: 0 });
+        }
+        
+        Self::padded(id, data)
+    }
+
+    /// Like [`Vector::new`], but also rejects data whose length isn't `expected_dim`
+    pub fn new_with_dim(
+        id: impl Into<String>,
+        data: Vec<f32>,
+        expected_dim: usize,
+    ) -> Result<Self, ZyphyrError> {
+        if data.len() != expected_dim {
+            return Err(ZyphyrError::InvalidDimension { expected: expected_dim, got: data.len() });
+        }
+        Self::new(id, data)
+    }
+
+
+    /// L2 norm of the real dimensions, computed once and cached for cosine distance
+    pub fn norm(&mut self) -> f32 {
+        match self.norm {
+            Some(norm) => norm,
+            None => {
+                let norm = crate::vector::distance::l2_norm(self.data());
+                self.norm = Some(norm);
+                norm
+            }
+        }
+    }
+
+    /// The cached norm, if [`Vector::norm`] has been called since the data last changed
+    pub fn cached_norm(&self) -> Option<f32> {
+        self.norm
+    }
+
     pub fn normalize(&mut self) {
         if self.is_normalized {
             return;
@@ -104,28 +286,195 @@ impl Vector {
             }
         }
         
+        // The data changed, so any cached norm is stale
+        self.norm = None;
+        self.original_norm = Some(magnitude);
         self.is_normalized = true;
     }
 
-    // Ensure memory alignment for SIMD
-    pub fn is_aligned(&self) -> bool {
-        let ptr = self.data.as_ptr() as *const u8;
-        is_aligned(ptr, SIMD_ALIGNMENT)
+    /// Undo [`Vector::normalize`] by scaling back to the magnitude recorded when
+    /// the vector was normalized. A no-op for vectors that aren't normalized or
+    /// whose original magnitude was zero.
+    pub fn denormalize(&mut self) {
+        let magnitude = match self.original_norm {
+            Some(magnitude) if self.is_normalized && magnitude > 0.0 => magnitude,
+            _ => return,
+        };
+        
+        for x in &mut self.data[..self.dim] {
+            *x *= magnitude;
+        }
+        self.norm = None;
+        self.original_norm = None;
+        self.is_normalized = false;
     }
+
     
-    // Add cache-friendly batch methods
+    /// Distance from this vector to `other` under `metric`
+    pub fn distance_to(&self, other: &Vector, metric: crate::DistanceMetric) -> Result<f32, ZyphyrError> {
+        metric.compute(self, other)
+    }
+
+    /// Element-wise sum over the real dimensions, producing a new vector with `id`
+    pub fn add(&self, other: &Vector, id: impl Into<String>) -> Result<Vector, ZyphyrError> {
+        self.zip_with(other, id, |a, b| a + b)
+    }
+
+    /// Element-wise difference `self - other`, producing a new vector with `id`
+    pub fn sub(&self, other: &Vector, id: impl Into<String>) -> Result<Vector, ZyphyrError> {
+        self.zip_with(other, id, |a, b| a - b)
+    }
+
+    /// Multiply every real dimension by `factor`, producing a new vector with `id`
+    ///
+    /// # Panics
+    ///
+    /// If `factor` is not finite or the product overflows to infinity, since a
+    /// `Vector` never holds non-finite values.
+    pub fn scale(&self, factor: f32, id: impl Into<String>) -> Vector {
+        let data: Vec<f32> = self.data().iter().map(|x| x * factor).collect();
+        if let Err(e) = check_finite(&data) {
+            panic!("Vector::scale by {} produced {}", factor, e);
+        }
+        // Allocation only fails if AlignedVec breaks its alignment guarantee
+        Self::padded(id.into(), &data).expect("AlignedVec returned a misaligned buffer")
+    }
+
+    fn zip_with(
+        &self,
+        other: &Vector,
+        id: impl Into<String>,
+        op: impl Fn(f32, f32) -> f32,
+    ) -> Result<Vector, ZyphyrError> {
+        if self.dim != other.dim {
+            return Err(ZyphyrError::InvalidDimension { expected: self.dim, got: other.dim });
+        }
+        let data: Vec<f32> = self.data()
+            .iter()
+            .zip(other.data().iter())
+            .map(|(&a, &b)| op(a, b))
+            .collect();
+        // Finite inputs can still overflow to infinity
+        check_finite(&data)?;
+        Self::padded(id.into(), &data)
+    }
+
+    /// Distances from this vector to each of `others`, in the same order. The
+    /// others are scored in cache-sized tiles while the query stays hot in L1;
+    /// with the `rayon` feature, large batches spread the tiles across threads.
     pub fn batch_distance(&self, others: &[&Vector], metric: crate::DistanceMetric) 
         -> Result<Vec<f32>, ZyphyrError> {
-        // Implementation for batch distance calculation
-        others.iter()
-            .map(|other| metric.compute(self, other))
-            .collect()
+        let tile_len = (BATCH_TILE_BYTES / (self.padded_dim * mem::size_of::<f32>())).max(1);
+        
+        #[cfg(feature = "rayon")]
+        if others.len() >= PARALLEL_MIN_TILES * tile_len {
+            use rayon::prelude::*;
+            
+            let tiles = others
+                .par_chunks(tile_len)
+                .map(|tile| self.tile_distances(tile, &metric))
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok(tiles.concat());
+        }
+        
+        let mut distances = Vec::with_capacity(others.len());
+        for tile in others.chunks(tile_len) {
+            distances.extend(self.tile_distances(tile, &metric)?);
+        }
+        Ok(distances)
     }
 
-    // Add memory usage tracking
-    pub fn memory_usage(&self) -> usize {
-        mem::size_of::<Self>() + 
-        self.id.capacity() +
-        self.padded_dim * mem::size_of::<f32>()
+    // Score one tile, prefetching each vector's data while the previous one is scored
+    fn tile_distances(&self, tile: &[&Vector], metric: &crate::DistanceMetric) -> Result<Vec<f32>, ZyphyrError> {
+        let mut distances = Vec::with_capacity(tile.len());
+        for (i, other) in tile.iter().enumerate() {
+            if let Some(next) = tile.get(i + 1) {
+                prefetch_read(next.raw_data());
+            }
+            distances.push(metric.compute(self, other)?);
+        }
+        Ok(distances)
+    }
+
+}
+
+// Allocate an empty buffer for `capacity` elements, verifying the SIMD alignment
+// the kernels rely on. `AVec` guarantees it, so a failure means the allocator broke that promise.
+fn aligned_buffer<T>(capacity: usize) -> Result<AlignedVec<T>, ZyphyrError> {
+    let buffer = AlignedVec::with_capacity(SIMD_ALIGNMENT, capacity);
+    let aligned = is_aligned(buffer.as_ptr() as *const u8, SIMD_ALIGNMENT);
+    debug_assert!(aligned, "AlignedVec returned memory not aligned to {} bytes", SIMD_ALIGNMENT);
+    if !aligned {
+        return Err(ZyphyrError::AllocationFailed(format!(
+            "buffer for {} elements is not aligned to {} bytes",
+            capacity, SIMD_ALIGNMENT
+        )));
+    }
+    Ok(buffer)
+}
+
+// NaN compares as equal to everything in search, so reject it up front
+pub(crate) fn check_finite<T: VectorElement>(data: &[T]) -> Result<(), ZyphyrError> {
+    match data.iter().position(|x| !x.is_finite()) {
+        Some(index) => Err(ZyphyrError::InvalidData(format!(
+            "non-finite value {:?} at index {}",
+            data[index], index
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Vectors compare equal when their ids match, regardless of their data or
+/// metadata. This makes `Vector` usable as a `HashSet`/`HashMap` key by id;
+/// compare [`Vector::data`] directly for content equality.
+impl<T: VectorElement> PartialEq for Vector<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T: VectorElement> Eq for Vector<T> {}
+
+/// Hashes the id only, consistent with the id-based `PartialEq`
+impl<T: VectorElement> Hash for Vector<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// Serializes as `{"id": ..., "data": [...]}` plus optional `metadata`. Only the
+/// real dimensions are written; the aligned, padded buffer is rebuilt on load.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Vector {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(serde::Serialize)]
+        struct Record<'a> {
+            id: &'a str,
+            data: &'a [f32],
+            #[serde(skip_serializing_if = "Option::is_none")]
+            metadata: Option<&'a Metadata>,
+        }
+        
+        Record { id: &self.id, data: self.data(), metadata: self.metadata.as_ref() }.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Vector {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        struct Record {
+            id: String,
+            data: Vec<f32>,
+            #[serde(default)]
+            metadata: Option<Metadata>,
+        }
+        
+        let record = Record::deserialize(deserializer)?;
+        let vector = Vector::new(record.id, record.data).map_err(serde::de::Error::custom)?;
+        Ok(match record.metadata {
+            Some(metadata) => vector.with_metadata(metadata),
+            None => vector,
+        })
     }
 }